@@ -0,0 +1,125 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::operations::OperationRegistry;
+use crate::{file_item, AppError, FileItem};
+
+const MAX_ACTIVITY_LIMIT: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentActivity {
+    operation_id: String,
+    items: Vec<FileItem>, // newest first
+    cancelled: bool,      // items then covers only the part walked so far
+}
+
+// The `limit` most recently modified files under `root` changed after
+// `since`, for a "what changed lately" view. Symlinks aren't followed.
+//
+// With `prune_stale_dirs`, folders not modified since `since` are skipped
+// unread. That's much faster on big trees, but a folder's time only moves
+// when entries are added, removed or renamed in it, so files edited in place
+// (and anything deeper) are missed there; leave it off for exact results.
+// Pass an `operation_id` to be able to `cancel_operation` the walk.
+#[tauri::command]
+pub async fn recent_activity(
+    root: String,
+    since: DateTime<Utc>,
+    limit: usize,
+    prune_stale_dirs: Option<bool>,
+    operation_id: Option<String>,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<RecentActivity, AppError> {
+    let root_path = PathBuf::from(&root);
+    
+    if !root_path.is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", root)));
+    }
+    
+    if limit > MAX_ACTIVITY_LIMIT {
+        return Err(AppError::InvalidInput(format!("limit is too large (max {})", MAX_ACTIVITY_LIMIT)));
+    }
+    
+    let (operation_id, cancelled) = registry.register(operation_id, "activity")?;
+    
+    let prune = prune_stale_dirs.unwrap_or(false);
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        walk_recent(&root_path, SystemTime::from(since), limit, prune, &cancelled)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Activity task failed: {}", e)));
+    
+    registry.finish(&operation_id);
+    
+    let (items, cancelled) = result?;
+    Ok(RecentActivity { operation_id, items, cancelled })
+}
+
+fn walk_recent(
+    root: &Path,
+    since: SystemTime,
+    limit: usize,
+    prune: bool,
+    cancelled: &AtomicBool,
+) -> (Vec<FileItem>, bool) {
+    // The newest `limit` so far, oldest on top so it's the one pushed out
+    let mut newest: BinaryHeap<Reverse<(SystemTime, PathBuf)>> = BinaryHeap::with_capacity(limit + 1);
+    let mut pending = vec![root.to_path_buf()];
+    let mut was_cancelled = false;
+    
+    'walk: while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue, // Unreadable directories are skipped
+        };
+        
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if cancelled.load(Ordering::Relaxed) {
+                was_cancelled = true;
+                break 'walk;
+            }
+            
+            let Ok(metadata) = fs::symlink_metadata(entry.path()) else {
+                continue;
+            };
+            let Ok(modified) = metadata.modified() else {
+                continue;
+            };
+            
+            if metadata.is_dir() {
+                if !prune || modified > since {
+                    pending.push(entry.path());
+                }
+            } else if metadata.is_file() && modified > since && limit > 0 {
+                // Only paths are kept; items are built once, for the final cut
+                let beats_oldest = newest.len() < limit
+                    || newest.peek().is_some_and(|Reverse((oldest, _))| modified > *oldest);
+                if beats_oldest {
+                    newest.push(Reverse((modified, entry.path())));
+                    if newest.len() > limit {
+                        newest.pop();
+                    }
+                }
+            }
+        }
+    }
+    
+    // Ascending by Reverse is newest first
+    let items = newest.into_sorted_vec()
+        .into_iter()
+        .filter_map(|Reverse((_, path))| {
+            let metadata = fs::symlink_metadata(&path).ok()?;
+            Some((path, metadata))
+        })
+        .enumerate()
+        .map(|(index, (path, metadata))| file_item(&path, &metadata, index.to_string()))
+        .collect();
+    
+    (items, was_cancelled)
+}
@@ -0,0 +1,638 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use zip::write::SimpleFileOptions;
+
+use crate::{encoding, temp_sibling_path, AppError, MAX_RANGE_BYTES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn parse(name: &str) -> Result<Self, AppError> {
+        match name.to_lowercase().as_str() {
+            "zip" => Ok(ArchiveFormat::Zip),
+            "tar" => Ok(ArchiveFormat::Tar),
+            "tar.gz" | "tgz" => Ok(ArchiveFormat::TarGz),
+            other => Err(AppError::Unsupported(format!(
+                "Unsupported archive format '{}', expected \"zip\", \"tar\" or \"tar.gz\"",
+                other
+            ))),
+        }
+    }
+    
+    // The extension wins when it's recognised; otherwise sniff the magic bytes
+    fn detect(path: &Path) -> Result<Self, AppError> {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if name.ends_with(".zip") {
+            return Ok(ArchiveFormat::Zip);
+        }
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            return Ok(ArchiveFormat::TarGz);
+        }
+        if name.ends_with(".tar") {
+            return Ok(ArchiveFormat::Tar);
+        }
+        
+        let mut header = Vec::with_capacity(512);
+        fs::File::open(path)
+            .and_then(|file| file.take(512).read_to_end(&mut header))
+            .map_err(|e| AppError::io("Failed to read archive", e))?;
+        
+        if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+            Ok(ArchiveFormat::Zip)
+        } else if header.starts_with(&[0x1f, 0x8b]) {
+            Ok(ArchiveFormat::TarGz)
+        } else if header.get(257..262) == Some(b"ustar") {
+            Ok(ArchiveFormat::Tar)
+        } else {
+            Err(AppError::Unsupported("Not a zip, tar or tar.gz archive".to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveProgress {
+    archive_path: String,
+    current_path: String,
+    entries_done: usize,
+    total_entries: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveSummary {
+    archive_path: String,
+    entry_count: usize,
+    size: u64,
+}
+
+struct ArchiveEntry {
+    source: PathBuf,
+    name: String, // "/"-separated path inside the archive
+    is_dir: bool,
+}
+
+// Each input is stored under its own name, so "~/photos" becomes "photos/..."
+// inside the archive. Files are streamed straight from disk into the encoder.
+// Symlinks are skipped rather than followed.
+#[tauri::command]
+pub async fn create_archive(
+    paths: Vec<String>,
+    output_path: String,
+    format: String,
+    overwrite: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<ArchiveSummary, AppError> {
+    let format = ArchiveFormat::parse(&format)?;
+    let output = PathBuf::from(&output_path);
+    
+    if output.exists() && !overwrite.unwrap_or(false) {
+        return Err(AppError::AlreadyExists("Output archive already exists".to_string()));
+    }
+    
+    if output.is_dir() {
+        return Err(AppError::IsADirectory("Output path is a directory".to_string()));
+    }
+    
+    let mut entries = Vec::new();
+    for path in &paths {
+        let source = Path::new(path);
+        let name = source.file_name()
+            .ok_or_else(|| AppError::InvalidInput(format!("Cannot archive '{}'", path)))?
+            .to_string_lossy()
+            .to_string();
+        collect_entries(source, name, &output, &mut entries)
+            .map_err(|e| AppError::io(&format!("Failed to read '{}'", path), e))?;
+    }
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        // Build next to the target and rename at the end, so a failure never
+        // leaves a truncated archive behind
+        let temp_path = temp_sibling_path(&output);
+        let progress = |entries_done: usize, entry: &ArchiveEntry| {
+            let _ = app_handle.emit("archive-progress", ArchiveProgress {
+                archive_path: output_path.clone(),
+                current_path: entry.source.to_string_lossy().to_string(),
+                entries_done,
+                total_entries: entries.len(),
+            });
+        };
+        
+        let result = match format {
+            ArchiveFormat::Zip => write_zip(&temp_path, &entries, progress),
+            ArchiveFormat::Tar => fs::File::create(&temp_path)
+                .and_then(|file| write_tar(file, &entries, progress))
+                .and_then(|file| file.sync_all()),
+            ArchiveFormat::TarGz => fs::File::create(&temp_path)
+                .and_then(|file| write_tar(GzEncoder::new(file, Compression::default()), &entries, progress))
+                .and_then(|encoder| encoder.finish())
+                .and_then(|file| file.sync_all()),
+        }
+        .and_then(|_| fs::rename(&temp_path, &output));
+        
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(AppError::io("Failed to create archive", e));
+        }
+        
+        Ok(ArchiveSummary {
+            archive_path: output_path.clone(),
+            entry_count: entries.len(),
+            size: fs::metadata(&output).map(|meta| meta.len()).unwrap_or(0),
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Archive task failed: {}", e)))?
+}
+
+fn collect_entries(
+    source: &Path,
+    name: String,
+    output: &Path,
+    entries: &mut Vec<ArchiveEntry>,
+) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(source)?;
+    
+    // Don't archive the archive being written (or links, which may point anywhere)
+    if metadata.file_type().is_symlink() || source == output {
+        return Ok(());
+    }
+    
+    if !metadata.is_dir() {
+        entries.push(ArchiveEntry { source: source.to_path_buf(), name, is_dir: false });
+        return Ok(());
+    }
+    
+    entries.push(ArchiveEntry { source: source.to_path_buf(), name: name.clone(), is_dir: true });
+    
+    let mut children: Vec<_> = fs::read_dir(source)?.filter_map(|entry| entry.ok()).collect();
+    children.sort_by_key(|entry| entry.file_name());
+    for child in children {
+        let child_name = format!("{}/{}", name, child.file_name().to_string_lossy());
+        collect_entries(&child.path(), child_name, output, entries)?;
+    }
+    
+    Ok(())
+}
+
+fn write_zip(
+    path: &Path,
+    entries: &[ArchiveEntry],
+    progress: impl Fn(usize, &ArchiveEntry),
+) -> io::Result<()> {
+    let mut writer = zip::ZipWriter::new(fs::File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.is_dir {
+            writer.add_directory(format!("{}/", entry.name), options).map_err(io::Error::other)?;
+        } else {
+            let mut file = fs::File::open(&entry.source)?;
+            let large_file = file.metadata()?.len() >= u32::MAX as u64;
+            writer.start_file(entry.name.as_str(), options.large_file(large_file))
+                .map_err(io::Error::other)?;
+            io::copy(&mut file, &mut writer)?;
+        }
+        progress(index + 1, entry);
+    }
+    
+    writer.finish().map_err(io::Error::other)?.sync_all()
+}
+
+fn write_tar<W: Write>(
+    writer: W,
+    entries: &[ArchiveEntry],
+    progress: impl Fn(usize, &ArchiveEntry),
+) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.is_dir {
+            builder.append_dir(&entry.name, &entry.source)?;
+        } else {
+            builder.append_path_with_name(&entry.source, &entry.name)?;
+        }
+        progress(index + 1, entry);
+    }
+    
+    builder.into_inner()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtractProgress {
+    archive_path: String,
+    current_path: String,
+    entries_done: usize,
+    total_entries: Option<usize>, // Unknown for tar streams until the end
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtractSummary {
+    archive_path: String,
+    destination: String,
+    entry_count: usize,
+    skipped: Vec<String>,
+}
+
+struct Extractor<'a> {
+    archive_path: &'a str,
+    root: PathBuf,
+    total_entries: Option<usize>,
+    entry_count: usize,
+    skipped: Vec<String>,
+    app_handle: &'a AppHandle,
+}
+
+// Unpacks a zip, tar or tar.gz into `destination`, creating it if needed.
+// Entries that would land outside `destination` (absolute paths, "..", or a
+// symlink already inside it) are never written and come back in `skipped`,
+// as do links and special files. Existing files are overwritten.
+#[tauri::command]
+pub async fn extract_archive(
+    archive_path: String,
+    destination: String,
+    app_handle: AppHandle,
+) -> Result<ExtractSummary, AppError> {
+    let archive = PathBuf::from(&archive_path);
+    
+    if !archive.is_file() {
+        return Err(AppError::NotFound("Archive does not exist".to_string()));
+    }
+    
+    let format = ArchiveFormat::detect(&archive)?;
+    
+    fs::create_dir_all(&destination)
+        .map_err(|e| AppError::io("Failed to create destination", e))?;
+    let root = fs::canonicalize(&destination)
+        .map_err(|e| AppError::io("Failed to resolve destination", e))?;
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut extractor = Extractor {
+            archive_path: &archive_path,
+            root,
+            total_entries: None,
+            entry_count: 0,
+            skipped: Vec::new(),
+            app_handle: &app_handle,
+        };
+        
+        let file = fs::File::open(&archive).map_err(|e| AppError::io("Failed to open archive", e))?;
+        match format {
+            ArchiveFormat::Zip => extractor.extract_zip(file)?,
+            ArchiveFormat::Tar => extractor.extract_tar(file)?,
+            ArchiveFormat::TarGz => extractor.extract_tar(GzDecoder::new(file))?,
+        }
+        
+        Ok(ExtractSummary {
+            archive_path: archive_path.clone(),
+            destination: extractor.root.to_string_lossy().to_string(),
+            entry_count: extractor.entry_count,
+            skipped: extractor.skipped,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Extract task failed: {}", e)))?
+}
+
+impl Extractor<'_> {
+    fn extract_zip(&mut self, file: fs::File) -> Result<(), AppError> {
+        let mut archive = zip::ZipArchive::new(io::BufReader::new(file))
+            .map_err(|e| AppError::InvalidInput(format!("Invalid zip archive: {}", e)))?;
+        self.total_entries = Some(archive.len());
+        
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid zip entry: {}", e)))?;
+            let name = entry.name().to_string();
+            
+            if entry.is_symlink() {
+                self.skip(name);
+                continue;
+            }
+            
+            let target = match self.resolve(Path::new(&name)) {
+                Some(target) => target,
+                None => {
+                    self.skip(name);
+                    continue;
+                }
+            };
+            
+            let result = if entry.is_dir() {
+                fs::create_dir_all(&target)
+            } else {
+                let mode = entry.unix_mode();
+                write_entry(&target, &mut entry, mode)
+            };
+            result.map_err(|e| AppError::io(&format!("Failed to extract '{}'", name), e))?;
+            self.extracted(&target);
+        }
+        
+        Ok(())
+    }
+    
+    fn extract_tar<R: Read>(&mut self, reader: R) -> Result<(), AppError> {
+        let mut archive = tar::Archive::new(reader);
+        let entries = archive.entries()
+            .map_err(|e| AppError::InvalidInput(format!("Invalid tar archive: {}", e)))?;
+        
+        for entry in entries {
+            let mut entry = entry.map_err(|e| AppError::InvalidInput(format!("Invalid tar entry: {}", e)))?;
+            let name = entry.path()
+                .map_err(|e| AppError::InvalidInput(format!("Invalid tar entry: {}", e)))?
+                .into_owned();
+            let entry_type = entry.header().entry_type();
+            
+            if !entry_type.is_dir() && !entry_type.is_file() {
+                self.skip(name.to_string_lossy().to_string());
+                continue;
+            }
+            
+            let target = match self.resolve(&name) {
+                Some(target) => target,
+                None => {
+                    self.skip(name.to_string_lossy().to_string());
+                    continue;
+                }
+            };
+            
+            let result = if entry_type.is_dir() {
+                fs::create_dir_all(&target)
+            } else {
+                let mode = entry.header().mode().ok();
+                write_entry(&target, &mut entry, mode)
+            };
+            result.map_err(|e| AppError::io(&format!("Failed to extract '{}'", name.display()), e))?;
+            self.extracted(&target);
+        }
+        
+        self.total_entries = Some(self.entry_count + self.skipped.len());
+        Ok(())
+    }
+    
+    // Maps an entry name onto the destination, or None if it would escape it.
+    // Parent directories are created here one level at a time, each checked
+    // before the next, so a symlink already among them (say "link" in
+    // "link/a/file", pointing elsewhere) stops the entry before any folder is
+    // made through it.
+    fn resolve(&self, name: &Path) -> Option<PathBuf> {
+        let relative = safe_relative(name)?;
+        let target = self.root.join(&relative);
+        
+        let mut parent = self.root.clone();
+        for component in relative.parent()?.components() {
+            parent.push(component);
+            match fs::symlink_metadata(&parent) {
+                Ok(meta) if meta.is_dir() => {}
+                Ok(_) => return None, // a symlink, or a file in the way
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => fs::create_dir(&parent).ok()?,
+                Err(_) => return None,
+            }
+        }
+        
+        // Never write through an existing link at the target itself
+        match fs::symlink_metadata(&target) {
+            Ok(meta) if meta.file_type().is_symlink() => None,
+            _ => Some(target),
+        }
+    }
+    
+    fn skip(&mut self, name: String) {
+        self.skipped.push(name);
+    }
+    
+    fn extracted(&mut self, target: &Path) {
+        self.entry_count += 1;
+        let _ = self.app_handle.emit("extract-progress", ExtractProgress {
+            archive_path: self.archive_path.to_string(),
+            current_path: target.to_string_lossy().to_string(),
+            entries_done: self.entry_count + self.skipped.len(),
+            total_entries: self.total_entries,
+        });
+    }
+}
+
+// An entry name as a plain relative path, or None when it's empty or could
+// escape a destination (absolute, or with "..")
+fn safe_relative(name: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+fn write_entry(target: &Path, reader: &mut impl Read, mode: Option<u32>) -> io::Result<()> {
+    let mut file = fs::File::create(target)?;
+    io::copy(reader, &mut file)?;
+    
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(mode & 0o777))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveListEntry {
+    name: String, // as stored, "/"-separated
+    size: u64,
+    compressed_size: Option<u64>, // zip only; tar entries aren't compressed one by one
+    is_dir: bool,
+    is_symlink: bool,
+    unsafe_path: bool, // would escape on extraction, so it can't be read either
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveEntryContent {
+    name: String,
+    content: String,      // base64
+    text: Option<String>, // decoded as well, unless the entry looks binary
+    encoding: Option<String>,
+    truncated: bool,
+    size: u64,
+}
+
+// Lists a zip, tar or tar.gz without extracting anything. A tar.gz has to be
+// decompressed from start to end to be listed.
+#[tauri::command]
+pub async fn list_archive(archive_path: String) -> Result<Vec<ArchiveListEntry>, AppError> {
+    let archive = PathBuf::from(&archive_path);
+    if !archive.is_file() {
+        return Err(AppError::NotFound("Archive does not exist".to_string()));
+    }
+    let format = ArchiveFormat::detect(&archive)?;
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::open(&archive).map_err(|e| AppError::io("Failed to open archive", e))?;
+        let mut entries = Vec::new();
+        
+        if format == ArchiveFormat::Zip {
+            let mut archive = open_zip(file)?;
+            for index in 0..archive.len() {
+                let entry = archive.by_index_raw(index)
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid zip entry: {}", e)))?;
+                entries.push(ArchiveListEntry {
+                    unsafe_path: safe_relative(Path::new(entry.name())).is_none(),
+                    name: entry.name().to_string(),
+                    size: entry.size(),
+                    compressed_size: Some(entry.compressed_size()),
+                    is_dir: entry.is_dir(),
+                    is_symlink: entry.is_symlink(),
+                });
+            }
+            return Ok(entries);
+        }
+        
+        let reader: Box<dyn Read> = match format {
+            ArchiveFormat::TarGz => Box::new(GzDecoder::new(file)),
+            _ => Box::new(file),
+        };
+        for_each_tar_entry(reader, |name, entry| {
+            let entry_type = entry.header().entry_type();
+            entries.push(ArchiveListEntry {
+                unsafe_path: safe_relative(name).is_none(),
+                name: name.to_string_lossy().to_string(),
+                size: entry.size(),
+                compressed_size: None,
+                is_dir: entry_type.is_dir(),
+                is_symlink: entry_type.is_symlink() || entry_type.is_hard_link(),
+            });
+            Ok(true)
+        })?;
+        Ok(entries)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Archive task failed: {}", e)))?
+}
+
+// Reads one file out of an archive, up to `max_bytes` (4MB by default).
+// `entry_name` is matched after the same clean-up extraction does, so
+// "./docs/a.txt" finds "docs/a.txt"; names that extraction would refuse are
+// refused here too.
+#[tauri::command]
+pub async fn read_archive_entry(
+    archive_path: String,
+    entry_name: String,
+    max_bytes: Option<u64>,
+) -> Result<ArchiveEntryContent, AppError> {
+    let archive = PathBuf::from(&archive_path);
+    if !archive.is_file() {
+        return Err(AppError::NotFound("Archive does not exist".to_string()));
+    }
+    let wanted = safe_relative(Path::new(&entry_name))
+        .ok_or_else(|| AppError::InvalidInput(format!("Refusing to read unsafe entry name '{}'", entry_name)))?;
+    
+    let max_bytes = max_bytes.unwrap_or(4 * 1024 * 1024);
+    if max_bytes > MAX_RANGE_BYTES {
+        return Err(AppError::InvalidInput(format!("max_bytes is too large (max {} bytes)", MAX_RANGE_BYTES)));
+    }
+    let format = ArchiveFormat::detect(&archive)?;
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::open(&archive).map_err(|e| AppError::io("Failed to open archive", e))?;
+        let not_found = || AppError::NotFound(format!("No entry named '{}' in the archive", entry_name));
+        let mut found: Option<(Vec<u8>, u64)> = None;
+        
+        if format == ArchiveFormat::Zip {
+            let mut archive = open_zip(file)?;
+            let index = (0..archive.len())
+                .find(|&index| {
+                    archive.name_for_index(index)
+                        .is_some_and(|name| safe_relative(Path::new(name)).as_ref() == Some(&wanted))
+                })
+                .ok_or_else(not_found)?;
+            let entry = archive.by_index(index)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid zip entry: {}", e)))?;
+            if entry.is_dir() || entry.is_symlink() {
+                return Err(AppError::InvalidInput(format!("'{}' is not a file", entry_name)));
+            }
+            let size = entry.size();
+            found = Some((read_capped(entry, max_bytes)?, size));
+        } else {
+            let reader: Box<dyn Read> = match format {
+                ArchiveFormat::TarGz => Box::new(GzDecoder::new(file)),
+                _ => Box::new(file),
+            };
+            for_each_tar_entry(reader, |name, entry| {
+                if safe_relative(name).as_ref() != Some(&wanted) {
+                    return Ok(true);
+                }
+                if !entry.header().entry_type().is_file() {
+                    return Err(AppError::InvalidInput(format!("'{}' is not a file", entry_name)));
+                }
+                let size = entry.size();
+                found = Some((read_capped(entry, max_bytes)?, size));
+                Ok(false)
+            })?;
+        }
+        
+        let (bytes, size) = found.ok_or_else(not_found)?;
+        let decoded = (!encoding::looks_binary(&bytes))
+            .then(|| encoding::decode_text(&bytes, None))
+            .transpose()?;
+        
+        Ok(ArchiveEntryContent {
+            name: wanted.to_string_lossy().replace('\\', "/"),
+            content: STANDARD.encode(&bytes),
+            encoding: decoded.as_ref().map(|decoded| decoded.encoding.name().to_string()),
+            text: decoded.map(|decoded| decoded.content),
+            truncated: size > bytes.len() as u64,
+            size,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Archive task failed: {}", e)))?
+}
+
+fn open_zip(file: fs::File) -> Result<zip::ZipArchive<io::BufReader<fs::File>>, AppError> {
+    zip::ZipArchive::new(io::BufReader::new(file))
+        .map_err(|e| AppError::InvalidInput(format!("Invalid zip archive: {}", e)))
+}
+
+// Calls `visit` with each entry's name until it returns false
+fn for_each_tar_entry<R: Read>(
+    reader: R,
+    mut visit: impl FnMut(&Path, &mut tar::Entry<R>) -> Result<bool, AppError>,
+) -> Result<(), AppError> {
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries()
+        .map_err(|e| AppError::InvalidInput(format!("Invalid tar archive: {}", e)))?;
+    
+    for entry in entries {
+        let mut entry = entry.map_err(|e| AppError::InvalidInput(format!("Invalid tar entry: {}", e)))?;
+        let name = entry.path()
+            .map_err(|e| AppError::InvalidInput(format!("Invalid tar entry: {}", e)))?
+            .into_owned();
+        if !visit(&name, &mut entry)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn read_capped(reader: impl Read, max_bytes: u64) -> Result<Vec<u8>, AppError> {
+    let mut bytes = Vec::new();
+    reader.take(max_bytes).read_to_end(&mut bytes)
+        .map_err(|e| AppError::io("Failed to read archive entry", e))?;
+    Ok(bytes)
+}
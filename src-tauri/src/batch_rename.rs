@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::operation_log::OperationLog;
+use crate::tags::TagStore;
+use crate::undo::UndoJournal;
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameStatus {
+    Planned,
+    Renamed,
+    Unchanged,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RenameEntry {
+    from: String,
+    to: String,
+    status: RenameStatus,
+    error: Option<String>,
+}
+
+// Renames every path using `pattern`, which may contain:
+//   {name}       original name without extension
+//   {ext}        original extension without the dot (".{ext}" vanishes when there is none)
+//   {index}      1-based position in `paths`, {index:03} pads it to three digits
+//   {date}       the file's modification date as YYYY-MM-DD
+// The whole batch is validated for collisions before anything is renamed.
+// With `preview` set nothing is touched and the planned mapping is returned.
+#[tauri::command]
+pub async fn batch_rename(
+    paths: Vec<String>,
+    pattern: String,
+    preview: Option<bool>,
+    tags: tauri::State<'_, TagStore>,
+    journal: tauri::State<'_, UndoJournal>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<Vec<RenameEntry>, AppError> {
+    let plan = plan_renames(&paths, &pattern)?;
+    
+    if preview.unwrap_or(false) {
+        return Ok(plan.into_iter()
+            .map(|(from, to)| {
+                let status = if from == to { RenameStatus::Unchanged } else { RenameStatus::Planned };
+                entry(&from, &to, status, None)
+            })
+            .collect());
+    }
+    
+    // Collisions were ruled out up front, so one failure doesn't block the rest
+    let mut renamed = Vec::new();
+    let entries = plan.into_iter()
+        .map(|(from, to)| {
+            if from == to {
+                return entry(&from, &to, RenameStatus::Unchanged, None);
+            }
+            let canonical = fs::canonicalize(&from).ok();
+            let result = fs::rename(&from, &to);
+            log.record("rename", &from, Some(&to), result.as_ref().err().map(|e| e.to_string()));
+            match result {
+                Ok(_) => {
+                    tags.move_path(canonical.as_deref(), &to);
+                    let renamed_entry = entry(&from, &to, RenameStatus::Renamed, None);
+                    renamed.push((from, to));
+                    renamed_entry
+                }
+                Err(e) => entry(&from, &to, RenameStatus::Failed, Some(e.to_string())),
+            }
+        })
+        .collect();
+    
+    journal.record_moves(format!("Rename {} items", renamed.len()), renamed);
+    
+    Ok(entries)
+}
+
+fn entry(from: &Path, to: &Path, status: RenameStatus, error: Option<String>) -> RenameEntry {
+    RenameEntry {
+        from: from.to_string_lossy().to_string(),
+        to: to.to_string_lossy().to_string(),
+        status,
+        error,
+    }
+}
+
+fn plan_renames(paths: &[String], pattern: &str) -> Result<Vec<(PathBuf, PathBuf)>, AppError> {
+    let sources: HashSet<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let mut targets: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let mut plan = Vec::with_capacity(paths.len());
+    let mut conflicts = Vec::new();
+    
+    for (index, path) in paths.iter().enumerate() {
+        let from = PathBuf::from(path);
+        
+        let metadata = fs::metadata(&from)
+            .map_err(|e| AppError::io(&format!("Cannot rename '{}'", path), e))?;
+        let parent = from.parent()
+            .ok_or_else(|| AppError::InvalidInput("Cannot rename root directory".to_string()))?;
+        
+        let name = render_pattern(pattern, &from, &metadata, index + 1)?;
+        if name.is_empty() || name.contains('/') || name.contains(std::path::MAIN_SEPARATOR) {
+            return Err(AppError::InvalidInput(format!("Pattern produced an invalid name '{}'", name)));
+        }
+        
+        let to = parent.join(&name);
+        
+        if let Some(other) = targets.insert(to.clone(), from.clone()) {
+            conflicts.push(format!("'{}' and '{}' would both become '{}'", other.display(), from.display(), name));
+        } else if to != from && to.exists() {
+            // Chained renames (a -> b while b -> c) would depend on ordering, so
+            // any target that is currently taken counts as a collision
+            let reason = if sources.contains(&to) { "another item in the batch" } else { "an existing item" };
+            conflicts.push(format!("'{}' would overwrite {} '{}'", from.display(), reason, name));
+        }
+        
+        plan.push((from, to));
+    }
+    
+    if !conflicts.is_empty() {
+        return Err(AppError::AlreadyExists(conflicts.join("; ")));
+    }
+    
+    Ok(plan)
+}
+
+fn render_pattern(pattern: &str, path: &Path, metadata: &fs::Metadata, index: usize) -> Result<String, AppError> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let extension = path.extension().map(|ext| ext.to_string_lossy().to_string());
+    
+    // Without an extension, ".{ext}" disappears entirely instead of leaving a trailing dot
+    let pattern = match extension {
+        Some(_) => pattern.to_string(),
+        None => pattern.replace(".{ext}", ""),
+    };
+    
+    let mut output = String::new();
+    let mut rest = pattern.as_str();
+    
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let end = rest[start..].find('}')
+            .map(|offset| start + offset)
+            .ok_or_else(|| AppError::InvalidInput("Unclosed '{' in rename pattern".to_string()))?;
+        
+        let token = &rest[start + 1..end];
+        match token {
+            "name" => output.push_str(&stem),
+            "ext" => output.push_str(extension.as_deref().unwrap_or("")),
+            "index" => output.push_str(&index.to_string()),
+            "date" => {
+                let modified = metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+                output.push_str(&DateTime::<Local>::from(modified).format("%Y-%m-%d").to_string());
+            }
+            _ => match token.strip_prefix("index:") {
+                Some(width) => {
+                    let width: usize = width.parse()
+                        .map_err(|_| AppError::InvalidInput(format!("Invalid index width in '{{{}}}'", token)))?;
+                    output.push_str(&format!("{:0width$}", index, width = width));
+                }
+                None => return Err(AppError::InvalidInput(format!("Unknown rename token '{{{}}}'", token))),
+            },
+        }
+        
+        rest = &rest[end + 1..];
+    }
+    
+    output.push_str(rest);
+    Ok(output)
+}
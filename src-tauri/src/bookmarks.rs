@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{write_atomic, AppError};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct Bookmark {
+    path: String, // canonical when added
+    label: Option<String>,
+    added: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BookmarkEntry {
+    path: String,
+    label: String, // the given label, or the folder name
+    added: DateTime<Utc>,
+    stale: bool, // the folder was moved or deleted since it was pinned
+}
+
+// Pinned folders, persisted in order as a JSON list in the app config folder.
+// Managed state rather than per-window storage, so every window sees the same
+// list; each change is broadcast as a `bookmarks-changed` event.
+pub struct BookmarkStore {
+    file: Option<PathBuf>, // None keeps bookmarks in memory only
+    bookmarks: Mutex<Vec<Bookmark>>,
+}
+
+impl BookmarkStore {
+    pub fn open(file: Option<PathBuf>) -> Self {
+        let bookmarks = file.as_ref()
+            .and_then(|file| fs::read(file).ok())
+            .and_then(|bytes| match serde_json::from_slice(&bytes) {
+                Ok(bookmarks) => Some(bookmarks),
+                Err(e) => {
+                    eprintln!("Ignoring unreadable bookmarks file: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        
+        BookmarkStore { file, bookmarks: Mutex::new(bookmarks) }
+    }
+    
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Vec<Bookmark>>, AppError> {
+        self.bookmarks.lock().map_err(|e| AppError::Internal(format!("Failed to lock bookmarks: {}", e)))
+    }
+    
+    fn save(&self, bookmarks: &[Bookmark]) -> Result<(), AppError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io("Failed to create bookmarks folder", e))?;
+        }
+        let json = serde_json::to_vec_pretty(bookmarks)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize bookmarks: {}", e)))?;
+        write_atomic(file, &json).map_err(|e| AppError::io("Failed to save bookmarks", e))
+    }
+}
+
+fn entries(bookmarks: &[Bookmark]) -> Vec<BookmarkEntry> {
+    bookmarks.iter()
+        .map(|bookmark| {
+            let path = Path::new(&bookmark.path);
+            let label = bookmark.label.clone().unwrap_or_else(|| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| bookmark.path.clone())
+            });
+            BookmarkEntry {
+                path: bookmark.path.clone(),
+                label,
+                added: bookmark.added,
+                stale: !path.is_dir(),
+            }
+        })
+        .collect()
+}
+
+fn save_and_broadcast(
+    store: &BookmarkStore,
+    bookmarks: &[Bookmark],
+    app_handle: &AppHandle,
+) -> Result<Vec<BookmarkEntry>, AppError> {
+    store.save(bookmarks)?;
+    let listed = entries(bookmarks);
+    let _ = app_handle.emit("bookmarks-changed", listed.clone());
+    Ok(listed)
+}
+
+// Pins a folder at the end of the list. Bookmarking a folder again only
+// updates its label. Returns the full list.
+#[tauri::command]
+pub async fn add_bookmark(
+    path: String,
+    label: Option<String>,
+    app_handle: AppHandle,
+    store: tauri::State<'_, BookmarkStore>,
+) -> Result<Vec<BookmarkEntry>, AppError> {
+    let canonical = fs::canonicalize(&path).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    if !canonical.is_dir() {
+        return Err(AppError::NotADirectory("Only folders can be bookmarked".to_string()));
+    }
+    
+    let key = canonical.to_string_lossy().to_string();
+    let label = label.map(|label| label.trim().to_string()).filter(|label| !label.is_empty());
+    
+    let mut bookmarks = store.lock()?;
+    match bookmarks.iter_mut().find(|bookmark| bookmark.path == key) {
+        Some(existing) => existing.label = label,
+        None => bookmarks.push(Bookmark { path: key, label, added: Utc::now() }),
+    }
+    
+    save_and_broadcast(&store, &bookmarks, &app_handle)
+}
+
+// Unpins by the path as listed, or by any path resolving to it. A stale
+// bookmark can only be removed by its listed path.
+#[tauri::command]
+pub async fn remove_bookmark(
+    path: String,
+    app_handle: AppHandle,
+    store: tauri::State<'_, BookmarkStore>,
+) -> Result<Vec<BookmarkEntry>, AppError> {
+    let canonical = fs::canonicalize(&path).ok().map(|canonical| canonical.to_string_lossy().to_string());
+    
+    let mut bookmarks = store.lock()?;
+    let before = bookmarks.len();
+    bookmarks.retain(|bookmark| bookmark.path != path && Some(&bookmark.path) != canonical.as_ref());
+    
+    if bookmarks.len() == before {
+        return Err(AppError::NotFound("No bookmark for this path".to_string()));
+    }
+    
+    save_and_broadcast(&store, &bookmarks, &app_handle)
+}
+
+#[tauri::command]
+pub async fn list_bookmarks(store: tauri::State<'_, BookmarkStore>) -> Result<Vec<BookmarkEntry>, AppError> {
+    Ok(entries(&store.lock()?))
+}
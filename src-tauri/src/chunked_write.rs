@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+use crate::{temp_sibling_path, AppError};
+
+// Files being written a chunk at a time, keyed by write id. Each goes to a
+// temporary sibling of its target until `finish_write` renames it into place,
+// so the target only ever holds the old content or the complete new one.
+#[derive(Default)]
+pub struct PendingWrites {
+    writes: Mutex<HashMap<String, SharedWrite>>,
+    next_id: AtomicU64,
+}
+
+struct PendingWrite {
+    target: PathBuf,
+    temp_path: PathBuf,
+    file: BufWriter<fs::File>,
+    bytes_written: u64,
+}
+
+type SharedWrite = Arc<Mutex<PendingWrite>>;
+
+impl PendingWrites {
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, SharedWrite>>, AppError> {
+        self.writes.lock().map_err(|e| AppError::Internal(format!("Failed to lock pending writes: {}", e)))
+    }
+    
+    fn get(&self, write_id: &str) -> Result<SharedWrite, AppError> {
+        self.lock()?
+            .get(write_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("No write in progress with id '{}'", write_id)))
+    }
+    
+    // Fails, leaving the write in place, while a chunk is still going in
+    fn take(&self, write_id: &str) -> Result<PendingWrite, AppError> {
+        let mut writes = self.lock()?;
+        let write = writes.remove(write_id)
+            .ok_or_else(|| AppError::NotFound(format!("No write in progress with id '{}'", write_id)))?;
+        
+        match Arc::try_unwrap(write) {
+            Ok(write) => write.into_inner().map_err(|e| AppError::Internal(format!("Failed to lock write: {}", e))),
+            Err(write) => {
+                writes.insert(write_id.to_string(), write);
+                Err(AppError::InvalidInput("A chunk is still being written".to_string()))
+            }
+        }
+    }
+}
+
+// Starts writing `path` a chunk at a time and returns the id to pass to the
+// other calls. An existing file is only replaced with `overwrite`.
+#[tauri::command]
+pub async fn begin_write(
+    path: String,
+    overwrite: Option<bool>,
+    writes: tauri::State<'_, PendingWrites>,
+) -> Result<String, AppError> {
+    let target = Path::new(&path);
+    
+    if target.is_dir() {
+        return Err(AppError::IsADirectory("Cannot write to directory".to_string()));
+    }
+    if target.exists() && !overwrite.unwrap_or(false) {
+        return Err(AppError::AlreadyExists("File already exists".to_string()));
+    }
+    
+    // Resolve symlinks so the link itself isn't replaced by a regular file
+    let target = fs::canonicalize(target).unwrap_or_else(|_| target.to_path_buf());
+    let temp_path = temp_sibling_path(&target);
+    let file = fs::File::create(&temp_path).map_err(|e| AppError::io("Failed to create file", e))?;
+    
+    let write_id = format!("write-{}", writes.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+    writes.lock()?.insert(write_id.clone(), Arc::new(Mutex::new(PendingWrite {
+        target,
+        temp_path,
+        file: BufWriter::new(file),
+        bytes_written: 0,
+    })));
+    
+    Ok(write_id)
+}
+
+// Appends base64 `data` and returns the total written so far. A failed write
+// ends the whole write and discards what it had.
+#[tauri::command]
+pub async fn write_chunk(
+    write_id: String,
+    data: String,
+    writes: tauri::State<'_, PendingWrites>,
+) -> Result<u64, AppError> {
+    let bytes = STANDARD.decode(data.as_bytes())
+        .map_err(|e| AppError::InvalidInput(format!("Invalid base64 chunk: {}", e)))?;
+    
+    let write = writes.get(&write_id)?;
+    let result = {
+        let mut write = write.lock().map_err(|e| AppError::Internal(format!("Failed to lock write: {}", e)))?;
+        write.file.write_all(&bytes).map(|_| {
+            write.bytes_written += bytes.len() as u64;
+            write.bytes_written
+        })
+    };
+    drop(write);
+    
+    result.map_err(|e| {
+        if let Ok(write) = writes.take(&write_id) {
+            discard(write);
+        }
+        AppError::io("Failed to write file", e)
+    })
+}
+
+// Flushes everything to disk and moves the file into place. Returns its size.
+#[tauri::command]
+pub async fn finish_write(write_id: String, writes: tauri::State<'_, PendingWrites>) -> Result<u64, AppError> {
+    let PendingWrite { target, temp_path, file, bytes_written } = writes.take(&write_id)?;
+    
+    let result = file.into_inner()
+        .map_err(|e| e.into_error())
+        .and_then(|file| {
+            file.sync_all()?;
+            if let Ok(metadata) = fs::metadata(&target) {
+                fs::set_permissions(&temp_path, metadata.permissions())?;
+            }
+            fs::rename(&temp_path, &target)
+        });
+    
+    match result {
+        Ok(()) => Ok(bytes_written),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(AppError::io("Failed to save file", e))
+        }
+    }
+}
+
+// Drops the write, leaving any existing file at the target untouched
+#[tauri::command]
+pub async fn abort_write(write_id: String, writes: tauri::State<'_, PendingWrites>) -> Result<(), AppError> {
+    discard(writes.take(&write_id)?);
+    Ok(())
+}
+
+fn discard(write: PendingWrite) {
+    let PendingWrite { temp_path, file, .. } = write;
+    drop(file);
+    let _ = fs::remove_file(temp_path);
+}
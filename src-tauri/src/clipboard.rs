@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+use arboard::Clipboard;
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+// The system clipboard handle, opened on first use. It's kept for the life of
+// the app because on Linux the copied data is served by whoever owns the
+// selection, and dropping the handle would make it vanish.
+#[derive(Default)]
+pub struct ClipboardState(Mutex<ClipboardInner>);
+
+#[derive(Default)]
+struct ClipboardInner {
+    handle: Option<Clipboard>,
+    // Files last placed on the clipboard with `cut` set, to recognise them
+    // when they're read back
+    cut_files: Option<Vec<PathBuf>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClipboardFiles {
+    paths: Vec<String>,
+    cut: bool,
+}
+
+impl ClipboardState {
+    fn with<T>(&self, action: impl FnOnce(&mut Clipboard, &mut Option<Vec<PathBuf>>) -> Result<T, arboard::Error>) -> Result<T, AppError> {
+        let mut inner = self.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock clipboard: {}", e)))?;
+        let inner = &mut *inner;
+        
+        if inner.handle.is_none() {
+            inner.handle = Some(Clipboard::new().map_err(clipboard_error)?);
+        }
+        
+        match inner.handle.as_mut() {
+            Some(clipboard) => action(clipboard, &mut inner.cut_files).map_err(clipboard_error),
+            None => Err(AppError::Internal("Clipboard is unavailable".to_string())),
+        }
+    }
+}
+
+fn clipboard_error(error: arboard::Error) -> AppError {
+    match error {
+        arboard::Error::ClipboardNotSupported => AppError::Unsupported("The clipboard is not available here".to_string()),
+        arboard::Error::ContentNotAvailable => AppError::NotFound("The clipboard holds no content of that kind".to_string()),
+        other => AppError::Io(format!("Clipboard error: {}", other)),
+    }
+}
+
+#[tauri::command]
+pub async fn copy_to_clipboard(text: String, state: tauri::State<'_, ClipboardState>) -> Result<(), AppError> {
+    state.with(|clipboard, cut_files| {
+        *cut_files = None;
+        clipboard.set_text(text)
+    })
+}
+
+// Copies paths as plain text, one per line, for pasting into a terminal or editor
+#[tauri::command]
+pub async fn copy_paths(paths: Vec<String>, state: tauri::State<'_, ClipboardState>) -> Result<(), AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput("No paths to copy".to_string()));
+    }
+    
+    state.with(|clipboard, cut_files| {
+        *cut_files = None;
+        clipboard.set_text(paths.join("\n"))
+    })
+}
+
+// Puts the files on the clipboard in the native format (NSPasteboard file
+// URLs, CF_HDROP, text/uri-list), so they can be pasted in Finder or
+// Explorer. `cut` is only tracked by this app: the system clipboard has no
+// portable way to mark files as cut, so other file managers paste a copy.
+#[tauri::command]
+pub async fn set_clipboard_files(
+    paths: Vec<String>,
+    cut: Option<bool>,
+    state: tauri::State<'_, ClipboardState>,
+) -> Result<(), AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput("No files to place on the clipboard".to_string()));
+    }
+    
+    let mut files = Vec::with_capacity(paths.len());
+    for path in &paths {
+        // Not canonicalized: Windows would hand Explorer "\\?\" verbatim paths
+        let absolute = std::path::absolute(path).map_err(|e| AppError::io(&format!("Cannot use '{}'", path), e))?;
+        if !absolute.exists() {
+            return Err(AppError::NotFound(format!("'{}' does not exist", path)));
+        }
+        files.push(absolute);
+    }
+    
+    state.with(|clipboard, cut_files| {
+        clipboard.set().file_list(&files)?;
+        *cut_files = cut.unwrap_or(false).then_some(files);
+        Ok(())
+    })
+}
+
+// Files currently on the system clipboard, whoever put them there. `cut` is
+// true only while they're still the ones this app placed with `cut` set.
+#[tauri::command]
+pub async fn get_clipboard_files(state: tauri::State<'_, ClipboardState>) -> Result<ClipboardFiles, AppError> {
+    state.with(|clipboard, cut_files| {
+        let files = match clipboard.get().file_list() {
+            Ok(files) => files,
+            Err(arboard::Error::ContentNotAvailable) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let cut = !files.is_empty() && cut_files.as_ref() == Some(&files);
+        
+        Ok(ClipboardFiles {
+            paths: files.iter().map(|file| file.to_string_lossy().to_string()).collect(),
+            cut,
+        })
+    })
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use serde::{Deserialize, Serialize};
+
+use crate::hashing::{hash_file, HashAlgorithm};
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DifferenceReason {
+    Type,     // a file on one side, a folder or symlink on the other
+    Size,
+    Modified, // same size, different mtime (only without `deep`)
+    Content,  // same size, different hash (only with `deep`)
+    Target,   // symlinks pointing at different places
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DifferingEntry {
+    path: String,
+    reason: DifferenceReason,
+}
+
+// Part of a tree that couldn't be compared, such as a folder without read
+// permission on either side. The rest of the comparison still runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComparisonError {
+    path: String,
+    error: String,
+}
+
+// Every path is relative to the two roots. A folder present on only one side
+// is listed once, without its contents.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DirectoryComparison {
+    only_in_left: Vec<String>,
+    only_in_right: Vec<String>,
+    differing: Vec<DifferingEntry>,
+    identical_count: usize,
+    errors: Vec<ComparisonError>,
+}
+
+// Compares two trees entry by entry. Files are judged by size and mtime, or
+// with `deep` by size and a content hash. Symlinks are compared by target and
+// never followed.
+#[tauri::command]
+pub async fn compare_directories(
+    left: String,
+    right: String,
+    deep: Option<bool>,
+) -> Result<DirectoryComparison, AppError> {
+    let left = PathBuf::from(left);
+    let right = PathBuf::from(right);
+    
+    for root in [&left, &right] {
+        if !root.is_dir() {
+            return Err(AppError::NotADirectory(format!("{} is not a directory", root.display())));
+        }
+    }
+    
+    let deep = deep.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut comparison = DirectoryComparison::default();
+        compare_level(&left, &right, Path::new(""), deep, &mut comparison);
+        comparison
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Comparison task failed: {}", e)))
+}
+
+fn compare_level(left: &Path, right: &Path, relative: &Path, deep: bool, comparison: &mut DirectoryComparison) {
+    let (Some(left_names), Some(right_names)) = (
+        entry_names(&left.join(relative), relative, comparison),
+        entry_names(&right.join(relative), relative, comparison),
+    ) else {
+        return;
+    };
+    
+    for name in left_names.union(&right_names) {
+        let path = relative.join(name);
+        let display = path.to_string_lossy().to_string();
+        
+        if !right_names.contains(name) {
+            comparison.only_in_left.push(display);
+            continue;
+        }
+        if !left_names.contains(name) {
+            comparison.only_in_right.push(display);
+            continue;
+        }
+        
+        match compare_entry(&left.join(&path), &right.join(&path), deep) {
+            Ok(Entry::Folders) => compare_level(left, right, &path, deep, comparison),
+            Ok(Entry::Same) => comparison.identical_count += 1,
+            Ok(Entry::Differs(reason)) => comparison.differing.push(DifferingEntry { path: display, reason }),
+            Err(e) => comparison.errors.push(ComparisonError { path: display, error: e.to_string() }),
+        }
+    }
+}
+
+fn entry_names(dir: &Path, relative: &Path, comparison: &mut DirectoryComparison) -> Option<BTreeSet<OsString>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => Some(entries.filter_map(|entry| entry.ok()).map(|entry| entry.file_name()).collect()),
+        Err(e) => {
+            comparison.errors.push(ComparisonError {
+                path: relative.to_string_lossy().to_string(),
+                error: format!("Failed to read {}: {}", dir.display(), e),
+            });
+            None
+        }
+    }
+}
+
+enum Entry {
+    Folders, // a real folder on both sides, to descend into
+    Same,
+    Differs(DifferenceReason),
+}
+
+fn compare_entry(left: &Path, right: &Path, deep: bool) -> Result<Entry, std::io::Error> {
+    let left_meta = fs::symlink_metadata(left)?;
+    let right_meta = fs::symlink_metadata(right)?;
+    let (left_type, right_type) = (left_meta.file_type(), right_meta.file_type());
+    
+    if left_type.is_symlink() && right_type.is_symlink() {
+        let same = fs::read_link(left)? == fs::read_link(right)?;
+        return Ok(if same { Entry::Same } else { Entry::Differs(DifferenceReason::Target) });
+    }
+    if left_type.is_symlink() || right_type.is_symlink() || left_type.is_dir() != right_type.is_dir() {
+        return Ok(Entry::Differs(DifferenceReason::Type));
+    }
+    if left_type.is_dir() {
+        return Ok(Entry::Folders);
+    }
+    
+    if left_meta.len() != right_meta.len() {
+        return Ok(Entry::Differs(DifferenceReason::Size));
+    }
+    
+    if deep {
+        let hash = |path: &Path| {
+            hash_file(path, HashAlgorithm::Blake3).map(|(digest, _)| digest)
+                .map_err(|e| std::io::Error::other(e.to_string()))
+        };
+        return Ok(if hash(left)? == hash(right)? { Entry::Same } else { Entry::Differs(DifferenceReason::Content) });
+    }
+    
+    // Whole seconds only: copies to FAT or network drives often lose the rest
+    let seconds = |meta: &fs::Metadata| {
+        meta.modified().ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_secs())
+    };
+    Ok(if seconds(&left_meta) == seconds(&right_meta) { Entry::Same } else { Entry::Differs(DifferenceReason::Modified) })
+}
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::operations::OperationRegistry;
+use crate::AppError;
+
+// Emit a progress event every this many entries
+const PROGRESS_INTERVAL: u64 = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DirectorySize {
+    operation_id: String,
+    path: String,
+    total_bytes: u64,
+    file_count: u64,
+    folder_count: u64,
+    cancelled: bool,
+}
+
+// Walks the subtree summing file sizes. Symlinks are never followed (so links
+// pointing outside the tree, or back into it, can't inflate the total or
+// cycle) and files with several hard links are only counted once.
+#[tauri::command]
+pub async fn get_directory_size(
+    path: String,
+    operation_id: Option<String>,
+    app_handle: AppHandle,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<DirectorySize, AppError> {
+    let root = PathBuf::from(&path);
+    
+    if !root.exists() {
+        return Err(AppError::NotFound("Directory does not exist".to_string()));
+    }
+    
+    if !root.is_dir() {
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let (operation_id, cancelled) = registry.register(operation_id, "dir-size")?;
+    
+    let mut size = DirectorySize {
+        operation_id: operation_id.clone(),
+        path,
+        ..Default::default()
+    };
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        walk_sizes(&root, &mut size, &cancelled, &app_handle);
+        size
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Directory size task failed: {}", e)));
+    
+    registry.finish(&operation_id);
+    
+    result
+}
+
+fn walk_sizes(root: &Path, size: &mut DirectorySize, cancelled: &AtomicBool, app_handle: &AppHandle) {
+    let mut pending = vec![root.to_path_buf()];
+    let mut seen_links = HashSet::new();
+    let mut entries_seen = 0u64;
+    
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue, // Unreadable directories are skipped
+        };
+        
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if cancelled.load(Ordering::Relaxed) {
+                size.cancelled = true;
+                return;
+            }
+            
+            let metadata = match fs::symlink_metadata(entry.path()) {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+            
+            if metadata.is_dir() {
+                size.folder_count += 1;
+                pending.push(entry.path());
+            } else if metadata.is_file() {
+                let first_link = hardlink_key(&entry.path(), &metadata).is_none_or(|key| seen_links.insert(key));
+                if first_link {
+                    size.file_count += 1;
+                    size.total_bytes += metadata.len();
+                }
+            }
+            
+            entries_seen += 1;
+            if entries_seen.is_multiple_of(PROGRESS_INTERVAL) {
+                let _ = app_handle.emit("dir-size-progress", size.clone());
+            }
+        }
+    }
+}
+
+// Identifies the underlying file for entries that have more than one hard link
+#[cfg(unix)]
+pub(crate) fn hardlink_key(_path: &Path, metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+// Windows metadata carries no link count, so each file is opened to ask
+#[cfg(not(unix))]
+pub(crate) fn hardlink_key(path: &Path, _metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    let (volume, index, links) = crate::identity::file_identity(path).ok()?;
+    (links > 1).then_some((volume, index))
+}
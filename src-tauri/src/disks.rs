@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiskUsage {
+    path: String,
+    mount_point: Option<String>,
+    // None when the filesystem doesn't report it, as with some network mounts
+    total_bytes: Option<u64>,
+    available_bytes: Option<u64>,
+    used_bytes: Option<u64>,
+}
+
+#[tauri::command]
+pub async fn get_disk_usage(path: String) -> Result<DiskUsage, AppError> {
+    let canonical = fs::canonicalize(&path).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    
+    let disks = Disks::new_with_refreshed_list();
+    let (mount_point, total, available) = match containing_disk(&disks, &canonical) {
+        Some(disk) => (
+            Some(disk.mount_point().to_string_lossy().to_string()),
+            Some(disk.total_space()).filter(|&total| total > 0),
+            Some(disk.available_space()).filter(|_| disk.total_space() > 0),
+        ),
+        None => (None, None, None),
+    };
+    
+    Ok(DiskUsage {
+        path,
+        mount_point,
+        total_bytes: total,
+        available_bytes: available,
+        used_bytes: total.zip(available).map(|(total, available)| total.saturating_sub(available)),
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Volume {
+    name: String,
+    mount_point: String,
+    file_system: String,
+    total_bytes: u64,
+    available_bytes: u64,
+    is_removable: bool,
+    is_read_only: bool,
+}
+
+// Mounted volumes a user would browse: drive letters on Windows, "/" and
+// /Volumes/* on macOS, and "/", /home and anything under /media, /run/media
+// or /mnt on Linux. System partitions, snaps and the like are left out.
+#[tauri::command]
+pub async fn list_volumes() -> Result<Vec<Volume>, AppError> {
+    let disks = Disks::new_with_refreshed_list();
+    
+    let mut volumes: Vec<Volume> = disks.list()
+        .iter()
+        .filter(|disk| disk.total_space() > 0 && is_user_volume(disk.mount_point()))
+        .map(|disk| Volume {
+            name: volume_name(disk),
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            file_system: disk.file_system().to_string_lossy().to_string(),
+            total_bytes: disk.total_space(),
+            available_bytes: disk.available_space(),
+            is_removable: disk.is_removable(),
+            is_read_only: disk.is_read_only(),
+        })
+        .collect();
+    
+    volumes.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    volumes.dedup_by(|a, b| a.mount_point == b.mount_point);
+    Ok(volumes)
+}
+
+// The volume label where the platform has one; Linux only knows the device
+// ("/dev/sdb1"), so the mount folder's name is used there instead
+fn volume_name(disk: &sysinfo::Disk) -> String {
+    let label = disk.name().to_string_lossy();
+    if !label.is_empty() && !label.starts_with("/dev/") {
+        return label.to_string();
+    }
+    
+    match disk.mount_point().file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => disk.mount_point().to_string_lossy().to_string(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn is_user_volume(mount_point: &Path) -> bool {
+    mount_point == Path::new("/") || mount_point.starts_with("/Volumes")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn is_user_volume(mount_point: &Path) -> bool {
+    mount_point == Path::new("/")
+        || mount_point == Path::new("/home")
+        || ["/media", "/run/media", "/mnt"].iter().any(|base| mount_point.starts_with(base))
+}
+
+#[cfg(not(unix))]
+fn is_user_volume(_mount_point: &Path) -> bool {
+    true
+}
+
+// Free space on the filesystem holding `path`, if it can be determined
+pub(crate) fn available_space(path: &Path) -> Option<u64> {
+    let canonical = fs::canonicalize(path).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    containing_disk(&disks, &canonical)
+        .filter(|disk| disk.total_space() > 0)
+        .map(|disk| disk.available_space())
+}
+
+// Whether the filesystem holding `path` is mounted read-only; false when
+// that can't be determined
+pub(crate) fn is_read_only_volume(path: &Path) -> bool {
+    let Ok(canonical) = fs::canonicalize(path) else {
+        return false;
+    };
+    let disks = Disks::new_with_refreshed_list();
+    containing_disk(&disks, &canonical).is_some_and(|disk| disk.is_read_only())
+}
+
+// The disk with the deepest mount point that contains `path`
+fn containing_disk<'a>(disks: &'a Disks, path: &Path) -> Option<&'a sysinfo::Disk> {
+    disks.list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+}
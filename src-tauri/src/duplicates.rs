@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::dir_size::hardlink_key;
+use crate::hashing::{hash_file, HashAlgorithm};
+use crate::operations::OperationRegistry;
+use crate::AppError;
+
+// Emit a progress event every this many files, in either phase
+const PROGRESS_INTERVAL: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DuplicateProgress {
+    operation_id: String,
+    phase: String, // "scanning", then "hashing"
+    files_done: usize,
+    files_total: Option<usize>, // known once hashing starts
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    size: u64,
+    digest: String, // blake3
+    paths: Vec<String>,
+    reclaimable_bytes: u64, // what deleting all but one copy would free
+}
+
+// Largest reclaimable space first
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DuplicateReport {
+    operation_id: String,
+    groups: Vec<DuplicateGroup>,
+    files_scanned: usize,
+    files_hashed: usize,
+    cancelled: bool,
+}
+
+// Finds files under `root` with identical content. Files are grouped by size
+// first and only those sharing a size are hashed. Symlinks aren't followed,
+// hard links to one file count once (removing them frees nothing), and files
+// smaller than `min_size` (1 byte by default, so empty files are left out) are
+// ignored. Unreadable files and folders are skipped.
+#[tauri::command]
+pub async fn find_duplicates(
+    root: String,
+    min_size: Option<u64>,
+    operation_id: Option<String>,
+    app_handle: AppHandle,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<DuplicateReport, AppError> {
+    let root = PathBuf::from(&root);
+    if !root.is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", root.display())));
+    }
+    
+    let (operation_id, cancelled) = registry.register(operation_id, "duplicates")?;
+    let min_size = min_size.unwrap_or(1);
+    
+    let task_id = operation_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let progress = |phase: &str, files_done: usize, files_total: Option<usize>| {
+            let _ = app_handle.emit("duplicates-progress", DuplicateProgress {
+                operation_id: task_id.clone(),
+                phase: phase.to_string(),
+                files_done,
+                files_total,
+            });
+        };
+        
+        let mut report = DuplicateReport { operation_id: task_id.clone(), ..Default::default() };
+        let by_size = group_by_size(&root, min_size, &cancelled, &mut report, &progress);
+        if report.cancelled {
+            return report;
+        }
+        
+        let candidates: Vec<(u64, Vec<PathBuf>)> = by_size.into_iter().filter(|(_, paths)| paths.len() > 1).collect();
+        let total: usize = candidates.iter().map(|(_, paths)| paths.len()).sum();
+        
+        for (size, paths) in candidates {
+            let mut by_digest: HashMap<String, Vec<String>> = HashMap::new();
+            for path in paths {
+                if cancelled.load(Ordering::Relaxed) {
+                    report.cancelled = true;
+                    return report;
+                }
+                if let Ok((digest, _)) = hash_file(&path, HashAlgorithm::Blake3) {
+                    by_digest.entry(digest).or_default().push(path.to_string_lossy().to_string());
+                }
+                report.files_hashed += 1;
+                if report.files_hashed.is_multiple_of(PROGRESS_INTERVAL) {
+                    progress("hashing", report.files_hashed, Some(total));
+                }
+            }
+            
+            report.groups.extend(by_digest.into_iter()
+                .filter(|(_, paths)| paths.len() > 1)
+                .map(|(digest, mut paths)| {
+                    paths.sort();
+                    DuplicateGroup { size, digest, reclaimable_bytes: size * (paths.len() as u64 - 1), paths }
+                }));
+        }
+        
+        report.groups.sort_by(|a, b| b.reclaimable_bytes.cmp(&a.reclaimable_bytes).then_with(|| a.paths.cmp(&b.paths)));
+        report
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Duplicate search task failed: {}", e)));
+    
+    registry.finish(&operation_id);
+    
+    result
+}
+
+fn group_by_size(
+    root: &Path,
+    min_size: u64,
+    cancelled: &AtomicBool,
+    report: &mut DuplicateReport,
+    progress: &impl Fn(&str, usize, Option<usize>),
+) -> HashMap<u64, Vec<PathBuf>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    let mut seen_links = HashSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if cancelled.load(Ordering::Relaxed) {
+                report.cancelled = true;
+                return by_size;
+            }
+            
+            let Ok(metadata) = fs::symlink_metadata(entry.path()) else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+            if !metadata.is_file() || metadata.len() < min_size {
+                continue;
+            }
+            if hardlink_key(&entry.path(), &metadata).is_some_and(|key| !seen_links.insert(key)) {
+                continue;
+            }
+            
+            by_size.entry(metadata.len()).or_default().push(entry.path());
+            report.files_scanned += 1;
+            if report.files_scanned.is_multiple_of(PROGRESS_INTERVAL) {
+                progress("scanning", report.files_scanned, None);
+            }
+        }
+    }
+    
+    by_size
+}
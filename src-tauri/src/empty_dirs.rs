@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::operation_log::OperationLog;
+use crate::tags::TagStore;
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanError {
+    path: String,
+    error: String,
+}
+
+// Deepest first, so each can be removed in order
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct EmptyDirectories {
+    directories: Vec<String>,
+    errors: Vec<ScanError>, // folders that couldn't be read, counted as not empty
+}
+
+// Finds the folders below `root` with nothing in them. With `deep`, a folder
+// holding only empty folders counts as empty too. Symlinks count as content
+// and are never followed. `root` itself is never reported.
+#[tauri::command]
+pub async fn find_empty_directories(root: String, deep: Option<bool>) -> Result<EmptyDirectories, AppError> {
+    let root = existing_dir(&root)?;
+    let deep = deep.unwrap_or(false);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut found = EmptyDirectories::default();
+        scan(&root, &root, deep, &mut found);
+        found
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Empty folder scan failed: {}", e)))
+}
+
+// Removes what `find_empty_directories` would report, bottom-up. A folder
+// that gained content since the scan is left alone and listed in `errors`.
+#[tauri::command]
+pub async fn remove_empty_directories(
+    root: String,
+    deep: Option<bool>,
+    tags: tauri::State<'_, TagStore>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<EmptyDirectories, AppError> {
+    let found = find_empty_directories(root, deep).await?;
+    
+    let mut removed = EmptyDirectories { directories: Vec::new(), errors: found.errors };
+    for dir in found.directories {
+        let result = fs::remove_dir(&dir);
+        log.record("delete", Path::new(&dir), None, result.as_ref().err().map(|e| e.to_string()));
+        match result {
+            Ok(()) => {
+                tags.forget_path(Path::new(&dir));
+                removed.directories.push(dir);
+            }
+            Err(e) => removed.errors.push(ScanError {
+                error: AppError::io("Failed to remove folder", e).to_string(),
+                path: dir,
+            }),
+        }
+    }
+    
+    Ok(removed)
+}
+
+fn existing_dir(path: &str) -> Result<PathBuf, AppError> {
+    if !Path::new(path).is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", path)));
+    }
+    // Canonical, so removals can clear tags
+    fs::canonicalize(path).map_err(|e| AppError::io("Failed to resolve path", e))
+}
+
+// Whether `dir` counts as empty
+fn scan(root: &Path, dir: &Path, deep: bool, found: &mut EmptyDirectories) -> bool {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            found.errors.push(ScanError {
+                path: dir.to_string_lossy().to_string(),
+                error: AppError::io("Failed to read folder", e).to_string(),
+            });
+            return false;
+        }
+    };
+    
+    let mut empty = true;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+        // Still descend past the first entry, to find empty folders further down
+        let child_empty = is_dir && scan(root, &entry.path(), deep, found);
+        if !(deep && child_empty) {
+            empty = false;
+        }
+    }
+    
+    if empty && dir != root {
+        found.directories.push(dir.to_string_lossy().to_string());
+    }
+    empty
+}
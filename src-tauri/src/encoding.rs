@@ -0,0 +1,126 @@
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+use crate::AppError;
+
+// Only the start of the buffer is inspected when guessing an encoding
+const SNIFF_BYTES: usize = 8 * 1024;
+
+pub struct DecodedText {
+    pub content: String,
+    pub encoding: &'static Encoding,
+    pub had_errors: bool,
+}
+
+// Decodes `bytes` using an explicit encoding label when given, otherwise a BOM,
+// a UTF-16 null-byte heuristic, strict UTF-8 validation and finally chardetng
+// for legacy encodings such as Windows-1252 or Shift_JIS.
+pub fn decode_text(bytes: &[u8], label: Option<&str>) -> Result<DecodedText, AppError> {
+    let (content, encoding, had_errors) = match label {
+        Some(label) => {
+            let encoding = for_label(label)?;
+            let (content, had_errors) = encoding.decode_with_bom_removal(bytes);
+            (content, encoding, had_errors)
+        }
+        None => detect_encoding(bytes).decode(bytes),
+    };
+    
+    Ok(DecodedText {
+        content: content.into_owned(),
+        encoding,
+        had_errors,
+    })
+}
+
+pub fn for_label(label: &str) -> Result<&'static Encoding, AppError> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| AppError::InvalidInput(format!("Unknown encoding '{}'", label)))
+}
+
+pub fn detect_encoding(bytes: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+    
+    let sample = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    
+    if let Some(encoding) = guess_utf16(sample) {
+        return encoding;
+    }
+    
+    // A truncated read may cut a multi-byte sequence at the very end; that's still UTF-8
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return UTF_8,
+        Err(e) if e.error_len().is_none() => return UTF_8,
+        Err(_) => {}
+    }
+    
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(sample, sample.len() == bytes.len());
+    detector.guess(None, true)
+}
+
+// "lf", "crlf" or "mixed" (only a lone "\r" is not counted as a line break),
+// and whether the text ends with a line break. Text without any line break
+// counts as "lf".
+pub fn line_endings(text: &str) -> (&'static str, bool) {
+    let crlf = text.matches("\r\n").count();
+    let lf = text.matches('\n').count() - crlf;
+    
+    let style = match (lf, crlf) {
+        (_, 0) => "lf",
+        (0, _) => "crlf",
+        _ => "mixed",
+    };
+    (style, text.ends_with('\n'))
+}
+
+// Rewrites every line break as `style` ("lf" or "crlf")
+pub fn convert_line_endings(text: &str, style: &str) -> Result<String, AppError> {
+    let normalized = text.replace("\r\n", "\n");
+    match style {
+        "lf" => Ok(normalized),
+        "crlf" => Ok(normalized.replace('\n', "\r\n")),
+        other => Err(AppError::InvalidInput(format!(
+            "Unknown line ending '{}', expected \"lf\" or \"crlf\"",
+            other
+        ))),
+    }
+}
+
+// Null bytes, or more than one byte in ten being a control character other
+// than tab, newline, form feed, carriage return or escape, mean the file isn't
+// text. UTF-16 (which is full of nulls) is recognized first and never binary.
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    if sample.is_empty() || Encoding::for_bom(sample).is_some() || guess_utf16(sample).is_some() {
+        return false;
+    }
+    
+    if sample.contains(&0) {
+        return true;
+    }
+    
+    let control = sample.iter()
+        .filter(|&&b| (b < 0x20 && !matches!(b, b'\t' | b'\n' | 0x0c | b'\r' | 0x1b)) || b == 0x7f)
+        .count();
+    control * 10 > sample.len()
+}
+
+// BOM-less UTF-16 text that's mostly ASCII has a null in every other byte
+fn guess_utf16(sample: &[u8]) -> Option<&'static Encoding> {
+    if sample.len() < 4 {
+        return None;
+    }
+    
+    let pairs = sample.len() / 2;
+    let even_nulls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd_nulls = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    
+    if odd_nulls * 10 > pairs * 4 && even_nulls * 10 < pairs {
+        Some(UTF_16LE)
+    } else if even_nulls * 10 > pairs * 4 && odd_nulls * 10 < pairs {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
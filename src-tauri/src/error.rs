@@ -0,0 +1,64 @@
+use std::fmt;
+use std::io::ErrorKind;
+use serde::Serialize;
+
+// Error returned by every command. Serializes as `{ code, message }` so the
+// frontend can branch on `code` and only show `message` to the user.
+#[derive(Debug, Serialize)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum AppError {
+    NotFound(String),
+    AlreadyExists(String),
+    PermissionDenied(String),
+    NotADirectory(String),
+    IsADirectory(String),
+    InvalidInput(String),
+    Unsupported(String),
+    Io(String),
+    Internal(String),
+}
+
+impl AppError {
+    // Wraps an io::Error with a short description of what was being attempted,
+    // keeping the machine-readable code derived from the error kind
+    pub fn io(context: &str, error: std::io::Error) -> Self {
+        Self::from_kind(error.kind(), format!("{}: {}", context, error))
+    }
+    
+    fn from_kind(kind: ErrorKind, message: String) -> Self {
+        match kind {
+            ErrorKind::NotFound => AppError::NotFound(message),
+            ErrorKind::AlreadyExists => AppError::AlreadyExists(message),
+            ErrorKind::PermissionDenied => AppError::PermissionDenied(message),
+            _ => AppError::Io(message),
+        }
+    }
+    
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::NotFound(message)
+            | AppError::AlreadyExists(message)
+            | AppError::PermissionDenied(message)
+            | AppError::NotADirectory(message)
+            | AppError::IsADirectory(message)
+            | AppError::InvalidInput(message)
+            | AppError::Unsupported(message)
+            | AppError::Io(message)
+            | AppError::Internal(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        Self::from_kind(error.kind(), error.to_string())
+    }
+}
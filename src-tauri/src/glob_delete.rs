@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::operation_log::OperationLog;
+use crate::tags::TagStore;
+use crate::trash_bin::{trash_one, TrashLedger};
+use crate::undo::UndoJournal;
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GlobDeleteFailure {
+    path: String,
+    error: String,
+}
+
+// With `dry_run`, `matched` is what would go and nothing else is filled in
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct GlobDeleteResult {
+    matched: Vec<String>,
+    deleted: Vec<String>,
+    failed: Vec<GlobDeleteFailure>,
+    dry_run: bool,
+}
+
+// Deletes everything under `root` whose name matches `pattern`, or whose path
+// relative to `root` does when the pattern contains a "/". A matching folder
+// goes as a whole and isn't searched further. Symlinks are matched but never
+// followed. Items go to the trash, as one undoable step, unless `permanent`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn delete_by_glob(
+    root: String,
+    pattern: String,
+    recursive: bool,
+    permanent: Option<bool>,
+    dry_run: Option<bool>,
+    ledger: tauri::State<'_, TrashLedger>,
+    journal: tauri::State<'_, UndoJournal>,
+    tags: tauri::State<'_, TagStore>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<GlobDeleteResult, AppError> {
+    if !Path::new(&root).is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", root)));
+    }
+    // Resolved up front so trash and tag bookkeeping see real paths
+    let root = fs::canonicalize(&root).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    
+    let trimmed = pattern.trim_matches('/');
+    let glob = glob::Pattern::new(trimmed)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid glob pattern: {}", e)))?;
+    let by_path = trimmed.contains('/');
+    
+    let matches = tauri::async_runtime::spawn_blocking({
+        let root = root.clone();
+        move || {
+            let mut matches = Vec::new();
+            collect_matches(&root, &root, &glob, by_path, recursive, &mut matches);
+            matches
+        }
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Glob delete task failed: {}", e)))?;
+    
+    let mut result = GlobDeleteResult {
+        matched: matches.iter().map(|path| path.to_string_lossy().to_string()).collect(),
+        dry_run: dry_run.unwrap_or(false),
+        ..Default::default()
+    };
+    if result.dry_run {
+        return Ok(result);
+    }
+    
+    let permanent = permanent.unwrap_or(false);
+    let mut trashed = Vec::new();
+    for path in matches {
+        let display = path.to_string_lossy().to_string();
+        let outcome = if permanent {
+            remove(&path).map(|_| tags.forget_path(&path))
+        } else {
+            trash_one(&path, &ledger).map(|trash_id| trashed.push((path.clone(), trash_id)))
+        };
+        log.record(if permanent { "delete" } else { "trash" }, &path, None, outcome.as_ref().err().cloned());
+        
+        match outcome {
+            Ok(()) => result.deleted.push(display),
+            Err(error) => result.failed.push(GlobDeleteFailure { path: display, error }),
+        }
+    }
+    
+    if !trashed.is_empty() {
+        journal.record_trash(format!("Trash {} items matching {}", trashed.len(), pattern), trashed);
+    }
+    
+    Ok(result)
+}
+
+fn collect_matches(
+    root: &Path,
+    dir: &Path,
+    glob: &glob::Pattern,
+    by_path: bool,
+    recursive: bool,
+    matches: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let matched = if by_path {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            glob.matches(&relative.to_string_lossy().replace('\\', "/"))
+        } else {
+            glob.matches(&entry.file_name().to_string_lossy())
+        };
+        
+        if matched {
+            matches.push(path);
+        } else if recursive && entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            collect_matches(root, &path, glob, by_path, recursive, matches);
+        }
+    }
+}
+
+fn remove(path: &Path) -> Result<(), String> {
+    let is_dir = fs::symlink_metadata(path).map(|meta| meta.is_dir()).unwrap_or(false);
+    let result = if is_dir { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    result.map_err(|e| AppError::io("Failed to delete item", e).to_string())
+}
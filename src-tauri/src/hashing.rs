@@ -0,0 +1,112 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::AppError;
+
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileHash {
+    algorithm: String,
+    digest: String,
+    size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub fn parse(name: Option<&str>) -> Result<Self, AppError> {
+        match name.map(|n| n.to_lowercase()).as_deref() {
+            None | Some("sha256") => Ok(HashAlgorithm::Sha256),
+            Some("blake3") => Ok(HashAlgorithm::Blake3),
+            Some(other) => Err(AppError::InvalidInput(format!("Unsupported hash algorithm '{}'", other))),
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+}
+
+// Streams the file through the hasher in fixed-size chunks so memory use stays
+// flat regardless of file size. Returns the hex digest and the bytes hashed.
+pub fn hash_file(path: &Path, algorithm: HashAlgorithm) -> Result<(String, u64), AppError> {
+    let mut file = fs::File::open(path).map_err(|e| AppError::io("Failed to open file", e))?;
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+    let mut size = 0u64;
+    
+    let mut hasher = StreamingHasher::new(algorithm);
+    
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| AppError::io("Failed to read file", e))?;
+        if read == 0 {
+            break;
+        }
+        size += read as u64;
+        hasher.update(&buffer[..read]);
+    }
+    
+    Ok((hasher.finalize_hex(), size))
+}
+
+enum StreamingHasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl StreamingHasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => StreamingHasher::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => StreamingHasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+    
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            StreamingHasher::Sha256(hasher) => hasher.update(data),
+            StreamingHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+    
+    fn finalize_hex(self) -> String {
+        match self {
+            StreamingHasher::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            StreamingHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn compute_file_hash(path: String, algorithm: Option<String>) -> Result<FileHash, AppError> {
+    let file_path = Path::new(&path);
+    
+    if !file_path.exists() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    
+    if file_path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot hash a directory".to_string()));
+    }
+    
+    let algorithm = HashAlgorithm::parse(algorithm.as_deref())?;
+    let (digest, size) = hash_file(file_path, algorithm)?;
+    
+    Ok(FileHash {
+        algorithm: algorithm.name().to_string(),
+        digest,
+        size,
+    })
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Mutex;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{imageops::FilterType, DynamicImage, ImageFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+const MAX_ICON_SIZE: u32 = 512;
+// Types whose icon is part of the file itself, so they're cached per path
+const OWN_ICON_EXTENSIONS: [&str; 7] = ["app", "desktop", "exe", "icns", "ico", "lnk", "url"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileIcon {
+    data_url: Option<String>, // "data:image/png;base64,...", size x size
+    // No icon is registered for the type; show a generic file or folder glyph
+    fallback: bool,
+    size: u32,
+    cached: bool,
+}
+
+// Icons already looked up this session, keyed by extension (or folder, or
+// path for types with their own icon) and size
+#[derive(Default)]
+pub struct IconCache(Mutex<HashMap<String, FileIcon>>);
+
+// The icon the platform shows for the item: LaunchServices on macOS, the shell
+// on Windows (which only has 16 and 32 pixel icons here, so larger sizes are
+// scaled up) and the icon theme on Linux (PNG icons only; themes with only
+// SVGs give the fallback).
+#[tauri::command]
+pub async fn get_file_icon(
+    path: String,
+    size: u32,
+    cache: tauri::State<'_, IconCache>,
+) -> Result<FileIcon, AppError> {
+    let item_path = Path::new(&path);
+    
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    if size == 0 || size > MAX_ICON_SIZE {
+        return Err(AppError::InvalidInput(format!("size must be between 1 and {}", MAX_ICON_SIZE)));
+    }
+    
+    let extension = item_path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+    let kind = match extension {
+        _ if item_path.is_dir() && extension.as_deref() != Some("app") => "folder".to_string(),
+        Some(ext) if OWN_ICON_EXTENSIONS.contains(&ext.as_str()) => path.clone(),
+        Some(ext) => format!(".{}", ext),
+        None => String::new(),
+    };
+    let key = format!("{}:{}", size, kind);
+    
+    if let Some(icon) = cache.0.lock().ok().and_then(|icons| icons.get(&key).cloned()) {
+        return Ok(FileIcon { cached: true, ..icon });
+    }
+    
+    let absolute = std::path::absolute(item_path).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    let png = tauri::async_runtime::spawn_blocking(move || native_icon(&absolute, size))
+        .await
+        .map_err(|e| AppError::Internal(format!("Icon task failed: {}", e)))??;
+    
+    let icon = FileIcon {
+        fallback: png.is_none(),
+        data_url: png.map(|png| format!("data:image/png;base64,{}", STANDARD.encode(png))),
+        size,
+        cached: false,
+    };
+    if let Ok(mut icons) = cache.0.lock() {
+        icons.insert(key, icon.clone());
+    }
+    Ok(icon)
+}
+
+// Scales to size x size and encodes as PNG
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn encode_icon(image: DynamicImage, size: u32) -> Result<Vec<u8>, AppError> {
+    let image = if image.width() == size && image.height() == size {
+        image
+    } else {
+        image.resize_exact(size, size, FilterType::Lanczos3)
+    };
+    
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| AppError::Internal(format!("Failed to encode icon: {}", e)))?;
+    Ok(png)
+}
+
+// NSWorkspace's icon drawn into a bitmap of the requested size, through a
+// JavaScript for Automation script. Types LaunchServices only knows by a
+// dynamic ("dyn.") identifier have no registered icon.
+#[cfg(target_os = "macos")]
+fn native_icon(path: &Path, size: u32) -> Result<Option<Vec<u8>>, AppError> {
+    const SCRIPT: &str = "function run(argv) { ObjC.import('AppKit'); \
+        const workspace = $.NSWorkspace.sharedWorkspace; \
+        const type = workspace.typeOfFileError(argv[0], null); \
+        if (type.isNil() || type.js.startsWith('dyn.')) return ''; \
+        const size = parseInt(argv[1]); \
+        const image = workspace.iconForFile(argv[0]); \
+        const rep = $.NSBitmapImageRep.alloc.initWithBitmapDataPlanesPixelsWidePixelsHighBitsPerSampleSamplesPerPixelHasAlphaIsPlanarColorSpaceNameBytesPerRowBitsPerPixel(\
+            null, size, size, 8, 4, true, false, 'NSDeviceRGBColorSpace', 0, 0); \
+        $.NSGraphicsContext.saveGraphicsState; \
+        $.NSGraphicsContext.currentContext = $.NSGraphicsContext.graphicsContextWithBitmapImageRep(rep); \
+        image.drawInRectFromRectOperationFraction($.NSMakeRect(0, 0, size, size), $.NSMakeRect(0, 0, 0, 0), 1, 1.0); \
+        $.NSGraphicsContext.restoreGraphicsState; \
+        return rep.representationUsingTypeProperties(4, $()).base64EncodedStringWithOptions(0).js; }";
+    
+    let output = std::process::Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", SCRIPT])
+        .arg(path)
+        .arg(size.to_string())
+        .output()
+        .map_err(|e| AppError::io("Failed to query LaunchServices", e))?;
+    if !output.status.success() {
+        return Err(AppError::Io(format!(
+            "Failed to get icon: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    
+    let encoded = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if encoded.is_empty() {
+        return Ok(None);
+    }
+    STANDARD.decode(encoded.as_bytes())
+        .map(Some)
+        .map_err(|e| AppError::Internal(format!("Unreadable icon data: {}", e)))
+}
+
+// SHGetFileInfo's icon, converted from the HICON's color bitmap. Types
+// without an association get Explorer's generic icon, so they count as
+// unregistered.
+#[cfg(windows)]
+fn native_icon(path: &Path, size: u32) -> Result<Option<Vec<u8>>, AppError> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_NORMAL;
+    use windows_sys::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON, SHGFI_SMALLICON};
+    use windows_sys::Win32::UI::WindowsAndMessaging::DestroyIcon;
+    
+    let has_own_icon = path.extension()
+        .is_some_and(|ext| OWN_ICON_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()));
+    if !has_own_icon && crate::launch::default_app(path)?.is_none() {
+        return Ok(None);
+    }
+    
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain([0]).collect();
+    let mut info = SHFILEINFOW::default();
+    let flags = SHGFI_ICON | if size <= 16 { SHGFI_SMALLICON } else { SHGFI_LARGEICON };
+    // SAFETY: `wide` is null-terminated and `info` is as large as we say
+    let found = unsafe {
+        SHGetFileInfoW(wide.as_ptr(), FILE_ATTRIBUTE_NORMAL, &mut info, std::mem::size_of::<SHFILEINFOW>() as u32, flags)
+    };
+    if found == 0 || info.hIcon.is_null() {
+        return Ok(None);
+    }
+    
+    let pixels = icon_pixels(info.hIcon);
+    // SAFETY: the icon came from SHGetFileInfoW, which leaves freeing it to us
+    unsafe { DestroyIcon(info.hIcon) };
+    
+    match pixels.and_then(|(width, height, rgba)| image::RgbaImage::from_raw(width, height, rgba)) {
+        Some(image) => encode_icon(DynamicImage::ImageRgba8(image), size).map(Some),
+        None => Ok(None),
+    }
+}
+
+// Width, height and RGBA pixels of an icon's color bitmap. None for the rare
+// monochrome icon, which has no color bitmap.
+#[cfg(windows)]
+fn icon_pixels(icon: windows_sys::Win32::UI::WindowsAndMessaging::HICON) -> Option<(u32, u32, Vec<u8>)> {
+    use windows_sys::Win32::Graphics::Gdi::{
+        CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetIconInfo, ICONINFO};
+    
+    // SAFETY: every handle used below comes from GetIconInfo or
+    // CreateCompatibleDC and is released before returning; GetDIBits writes
+    // at most `height` rows of 32-bit pixels, which is what `bgra` holds
+    unsafe {
+        let mut info = ICONINFO::default();
+        if GetIconInfo(icon, &mut info) == 0 {
+            return None;
+        }
+        
+        let dc = CreateCompatibleDC(std::ptr::null_mut());
+        let mut bitmap = BITMAPINFO::default();
+        bitmap.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+        
+        // The first call only fills in the bitmap's dimensions
+        let mut pixels = None;
+        if !info.hbmColor.is_null() && GetDIBits(dc, info.hbmColor, 0, 0, std::ptr::null_mut(), &mut bitmap, DIB_RGB_COLORS) != 0 {
+            let width = bitmap.bmiHeader.biWidth;
+            let height = bitmap.bmiHeader.biHeight.abs();
+            bitmap.bmiHeader.biHeight = -height; // top-down rows
+            bitmap.bmiHeader.biBitCount = 32;
+            bitmap.bmiHeader.biCompression = BI_RGB;
+            bitmap.bmiHeader.biPlanes = 1;
+            
+            let mut bgra = vec![0u8; width as usize * height as usize * 4];
+            let rows = GetDIBits(dc, info.hbmColor, 0, height as u32, bgra.as_mut_ptr().cast(), &mut bitmap, DIB_RGB_COLORS);
+            if rows == height {
+                // Old icons carry no alpha at all; those are fully opaque
+                let has_alpha = bgra.chunks_exact(4).any(|pixel| pixel[3] != 0);
+                for pixel in bgra.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                    if !has_alpha {
+                        pixel[3] = 255;
+                    }
+                }
+                pixels = Some((width as u32, height as u32, bgra));
+            }
+        }
+        
+        DeleteDC(dc);
+        if !info.hbmColor.is_null() {
+            DeleteObject(info.hbmColor);
+        }
+        DeleteObject(info.hbmMask);
+        pixels
+    }
+}
+
+// The freedesktop naming: "text/x-python" becomes "text-x-python", then the
+// generic "text-x-generic". Looked up in the user's theme (per GNOME's
+// setting), then Adwaita, breeze and hicolor, nearest size first.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn native_icon(path: &Path, size: u32) -> Result<Option<Vec<u8>>, AppError> {
+    let names: Vec<String> = if path.is_dir() {
+        vec!["folder".to_string(), "inode-directory".to_string()]
+    } else {
+        let mime = crate::mime::sniff_file(path)?.mime;
+        let major = mime.split('/').next().unwrap_or_default().to_string();
+        vec![mime.replace('/', "-"), format!("{}-x-generic", major)]
+    };
+    
+    let current_theme = std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "icon-theme"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().trim_matches('\'').to_string())
+        .filter(|theme| !theme.is_empty());
+    let themes: Vec<String> = current_theme.into_iter()
+        .chain(["Adwaita", "breeze", "hicolor"].map(str::to_string))
+        .collect();
+    
+    let mut bases: Vec<std::path::PathBuf> = dirs::home_dir().map(|home| home.join(".icons")).into_iter().collect();
+    bases.extend(crate::launch::xdg_data_dirs().into_iter().map(|dir| dir.join("icons")));
+    
+    // Exact size, then larger ones (scaled down), then smaller ones
+    const THEME_SIZES: [u32; 10] = [16, 22, 24, 32, 48, 64, 96, 128, 256, 512];
+    let mut sizes: Vec<u32> = THEME_SIZES.iter().copied().filter(|s| *s >= size).collect();
+    sizes.extend(THEME_SIZES.iter().rev().copied().filter(|s| *s < size));
+    
+    for name in &names {
+        for theme in &themes {
+            for icon_size in &sizes {
+                for context in ["mimetypes", "places"] {
+                    for base in &bases {
+                        let candidate = base.join(theme)
+                            .join(format!("{0}x{0}", icon_size))
+                            .join(context)
+                            .join(format!("{}.png", name));
+                        if let Ok(image) = image::open(&candidate) {
+                            return encode_icon(image, size).map(Some);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
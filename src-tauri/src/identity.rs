@@ -0,0 +1,76 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+// Two paths are the same underlying file exactly when their `file_id`s
+// match: hard links of one file, or a symlink and its target. Ids can be
+// reused once a file is deleted, so they're only meaningful while both
+// files exist.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileIdentity {
+    path: String,
+    file_id: String, // opaque; compare, don't parse
+    device: u64,     // st_dev on Unix, the volume serial number on Windows
+    inode: u64,      // st_ino on Unix, nFileIndexHigh/Low on Windows
+    link_count: u64,
+}
+
+// Symlinks are followed
+#[tauri::command]
+pub async fn get_file_identity(path: String) -> Result<FileIdentity, AppError> {
+    let item_path = Path::new(&path);
+    if !item_path.exists() {
+        return Err(AppError::NotFound(format!("{} does not exist", path)));
+    }
+    
+    let (device, inode, link_count) = file_identity(item_path)
+        .map_err(|e| AppError::io("Failed to read file identity", e))?;
+    
+    Ok(FileIdentity {
+        path: item_path.to_string_lossy().to_string(),
+        file_id: format!("{:x}-{:x}", device, inode),
+        device,
+        inode,
+        link_count,
+    })
+}
+
+#[cfg(unix)]
+pub(crate) fn file_identity(path: &Path) -> io::Result<(u64, u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.dev(), metadata.ino(), metadata.nlink()))
+}
+
+// The file index needs an open handle. Opened without read access, so files
+// the user can't read still have an identity, and with backup semantics,
+// which is what lets a folder be opened at all.
+#[cfg(windows)]
+pub(crate) fn file_identity(path: &Path) -> io::Result<(u64, u64, u64)> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_FLAG_BACKUP_SEMANTICS,
+    };
+    
+    let file = fs::OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(crate::paths::extended_path(path))?;
+    
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    if unsafe { GetFileInformationByHandle(file.as_raw_handle(), &mut info) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    
+    let index = ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64;
+    Ok((info.dwVolumeSerialNumber as u64, index, info.nNumberOfLinks as u64))
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn file_identity(_path: &Path) -> io::Result<(u64, u64, u64)> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "File identities aren't available on this platform"))
+}
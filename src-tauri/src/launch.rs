@@ -0,0 +1,460 @@
+use std::path::Path;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DefaultApp {
+    name: String,
+    // The `.app` bundle, executable or `.desktop` entry, as `open_with` takes it
+    path: Option<String>,
+}
+
+// Opens the platform file manager on the item's parent folder with the item
+// selected. Works the same for files and folders.
+#[tauri::command]
+pub async fn reveal_in_file_manager(path: String) -> Result<String, AppError> {
+    let item_path = Path::new(&path);
+    
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    let absolute = std::path::absolute(item_path)
+        .map_err(|e| AppError::io("Failed to resolve path", e))?;
+    reveal(&absolute)?;
+    
+    Ok(format!("Revealed '{}' in the file manager", absolute.file_name().unwrap_or_default().to_string_lossy()))
+}
+
+#[cfg(target_os = "macos")]
+fn reveal(path: &Path) -> Result<(), AppError> {
+    let status = Command::new("open").arg("-R").arg(path).status()
+        .map_err(|e| AppError::io("Failed to launch Finder", e))?;
+    if !status.success() {
+        return Err(AppError::Io(format!("Finder exited with {}", status)));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn reveal(path: &Path) -> Result<(), AppError> {
+    use std::os::windows::process::CommandExt;
+    
+    // Explorer parses its own command line and wants the path quoted right
+    // after the comma. It exits non-zero even on success, so only a failure
+    // to launch counts.
+    Command::new("explorer")
+        .raw_arg(format!("/select,\"{}\"", path.display()))
+        .spawn()
+        .map_err(|e| AppError::io("Failed to launch Explorer", e))?;
+    Ok(())
+}
+
+// The freedesktop FileManager1 D-Bus interface selects the item in Nautilus,
+// Dolphin, Nemo and most others. Without it, open the parent folder instead.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal(path: &Path) -> Result<(), AppError> {
+    let shown = Command::new("dbus-send")
+        .args([
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "--type=method_call",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+        ])
+        .arg(format!("array:string:{}", file_uri(path)))
+        .arg("string:")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    
+    if shown {
+        return Ok(());
+    }
+    
+    let folder = path.parent().unwrap_or(path);
+    open::that(folder).map_err(|e| AppError::io("Failed to open the file manager", e))
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn file_uri(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+// Opens a terminal window in the folder: iTerm when installed on macOS,
+// otherwise Terminal; Windows Terminal, falling back to a cmd console; on
+// Linux $TERMINAL, then the Debian-style x-terminal-emulator alternative, then
+// the first common emulator found.
+#[tauri::command]
+pub async fn open_terminal(path: String) -> Result<String, AppError> {
+    let dir = Path::new(&path);
+    
+    if !dir.is_dir() {
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let absolute = std::path::absolute(dir).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    let terminal = launch_terminal(&absolute)?;
+    
+    Ok(format!("Opened {} in '{}'", terminal, absolute.display()))
+}
+
+#[cfg(target_os = "macos")]
+fn launch_terminal(dir: &Path) -> Result<String, AppError> {
+    let app = if Path::new("/Applications/iTerm.app").exists() { "iTerm" } else { "Terminal" };
+    let output = Command::new("open").arg("-a").arg(app).arg(dir).output()
+        .map_err(|e| AppError::io("Failed to launch the terminal", e))?;
+    if !output.status.success() {
+        return Err(AppError::NotFound(format!(
+            "Failed to open {}: {}",
+            app,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(app.to_string())
+}
+
+#[cfg(windows)]
+fn launch_terminal(dir: &Path) -> Result<String, AppError> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NEW_CONSOLE: u32 = 0x10;
+    
+    // "-d ." rather than the path itself: wt mangles a quoted path that ends
+    // in a backslash, such as a drive root
+    match Command::new("wt").args(["-d", "."]).current_dir(dir).spawn() {
+        Ok(_) => return Ok("Windows Terminal".to_string()),
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            return Err(AppError::io("Failed to launch Windows Terminal", e));
+        }
+        Err(_) => {}
+    }
+    
+    Command::new("cmd")
+        .arg("/K")
+        .current_dir(dir)
+        .creation_flags(CREATE_NEW_CONSOLE)
+        .spawn()
+        .map_err(|e| AppError::io("Failed to launch cmd", e))?;
+    Ok("cmd".to_string())
+}
+
+// Every emulator here starts in the working directory it's launched from
+#[cfg(all(unix, not(target_os = "macos")))]
+fn launch_terminal(dir: &Path) -> Result<String, AppError> {
+    const FALLBACKS: [&str; 9] = [
+        "x-terminal-emulator",
+        "gnome-terminal",
+        "konsole",
+        "xfce4-terminal",
+        "kitty",
+        "alacritty",
+        "foot",
+        "wezterm",
+        "xterm",
+    ];
+    
+    // $TERMINAL may carry arguments of its own, like "kitty --single-instance"
+    let configured = std::env::var("TERMINAL").ok().filter(|terminal| !terminal.trim().is_empty());
+    let candidates = configured.iter().map(String::as_str).chain(FALLBACKS);
+    
+    for candidate in candidates {
+        let mut words = candidate.split_whitespace();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        match Command::new(program).args(words).current_dir(dir).spawn() {
+            Ok(_) => return Ok(program.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(AppError::io(&format!("Failed to launch {}", program), e)),
+        }
+    }
+    
+    Err(AppError::NotFound(
+        "No terminal emulator found; set $TERMINAL to the one you use".to_string(),
+    ))
+}
+
+// Opens the file with a chosen application instead of the default one: a
+// macOS `.app` bundle (or app name), a Windows executable, or on Linux either
+// a `.desktop` entry or a plain executable. The app is left running.
+#[tauri::command]
+pub async fn open_with(file_path: String, app_path: String) -> Result<String, AppError> {
+    let file = Path::new(&file_path);
+    
+    if !file.exists() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    
+    if app_path.trim().is_empty() {
+        return Err(AppError::InvalidInput("No application given".to_string()));
+    }
+    
+    let absolute = std::path::absolute(file)
+        .map_err(|e| AppError::io("Failed to resolve path", e))?;
+    launch_with(&absolute, Path::new(&app_path))?;
+    
+    Ok(format!(
+        "Opened '{}' with {}",
+        absolute.file_name().unwrap_or_default().to_string_lossy(),
+        Path::new(&app_path).file_stem().unwrap_or_default().to_string_lossy()
+    ))
+}
+
+#[cfg(target_os = "macos")]
+fn launch_with(file: &Path, app: &Path) -> Result<(), AppError> {
+    // `open -a` accepts bundle paths and names alike and reports unknown apps
+    let output = Command::new("open").arg("-a").arg(app).arg(file).output()
+        .map_err(|e| AppError::io("Failed to launch application", e))?;
+    if !output.status.success() {
+        return Err(AppError::NotFound(format!(
+            "Failed to open with application: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn launch_with(file: &Path, app: &Path) -> Result<(), AppError> {
+    Command::new(app)
+        .arg(file)
+        .spawn()
+        .map_err(|e| AppError::io("Failed to launch application", e))?;
+    Ok(())
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn launch_with(file: &Path, app: &Path) -> Result<(), AppError> {
+    let (program, args) = if app.extension().is_some_and(|ext| ext == "desktop") {
+        desktop_entry_command(app, file)?
+    } else {
+        (app.to_string_lossy().to_string(), vec![file.to_string_lossy().to_string()])
+    };
+    
+    Command::new(&program)
+        .args(&args)
+        .spawn()
+        .map_err(|e| AppError::io(&format!("Failed to launch {}", program), e))?;
+    Ok(())
+}
+
+// The program and arguments from a desktop entry's `Exec` line, with the file
+// substituted for its %f/%F/%u/%U field code (or appended when there's none)
+// and the other field codes dropped, per the Desktop Entry spec.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_entry_command(entry: &Path, file: &Path) -> Result<(String, Vec<String>), AppError> {
+    let contents = std::fs::read_to_string(entry)
+        .map_err(|e| AppError::io("Failed to read desktop entry", e))?;
+    
+    let exec = desktop_entry_value(&contents, "Exec")
+        .ok_or_else(|| AppError::InvalidInput("Desktop entry has no Exec line".to_string()))?;
+    
+    let mut words = Vec::new();
+    let mut substituted = false;
+    for word in split_exec(exec) {
+        match word.as_str() {
+            "%f" | "%F" => words.push(file.to_string_lossy().to_string()),
+            "%u" | "%U" => words.push(file_uri(file)),
+            _ if word.len() == 2 && word.starts_with('%') && word != "%%" => continue,
+            _ => {
+                words.push(word.replace("%%", "%"));
+                continue;
+            }
+        }
+        substituted = true;
+    }
+    if !substituted {
+        words.push(file.to_string_lossy().to_string());
+    }
+    
+    let mut words = words.into_iter();
+    let program = words.next()
+        .ok_or_else(|| AppError::InvalidInput("Desktop entry has an empty Exec line".to_string()))?;
+    Ok((program, words.collect()))
+}
+
+// A key from the [Desktop Entry] group, ignoring localized variants
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_entry_value<'a>(contents: &'a str, key: &str) -> Option<&'a str> {
+    contents.lines()
+        .skip_while(|line| line.trim() != "[Desktop Entry]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+}
+
+// Splits on whitespace, honouring double quotes and backslash escapes inside them
+#[cfg(all(unix, not(target_os = "macos")))]
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    let mut chars = exec.chars();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            '\\' if quoted => current.extend(chars.next()),
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    words
+}
+
+// The application `open_file_with_default_app` would hand the file to, or
+// None when the file type has no association
+#[tauri::command]
+pub async fn get_default_app(path: String) -> Result<Option<DefaultApp>, AppError> {
+    let item_path = Path::new(&path);
+    
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    let absolute = std::path::absolute(item_path)
+        .map_err(|e| AppError::io("Failed to resolve path", e))?;
+    tauri::async_runtime::spawn_blocking(move || default_app(&absolute))
+        .await
+        .map_err(|e| AppError::Internal(format!("Default app lookup failed: {}", e)))?
+}
+
+// LaunchServices, through NSWorkspace from a JavaScript for Automation script
+#[cfg(target_os = "macos")]
+pub(crate) fn default_app(path: &Path) -> Result<Option<DefaultApp>, AppError> {
+    const SCRIPT: &str = "function run(argv) { ObjC.import('AppKit'); \
+        const app = $.NSWorkspace.sharedWorkspace.URLForApplicationToOpenURL($.NSURL.fileURLWithPath(argv[0])); \
+        return app.isNil() ? '' : app.path.js; }";
+    
+    let output = Command::new("osascript").args(["-l", "JavaScript", "-e", SCRIPT]).arg(path).output()
+        .map_err(|e| AppError::io("Failed to query LaunchServices", e))?;
+    if !output.status.success() {
+        return Err(AppError::Io(format!(
+            "Failed to query LaunchServices: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    
+    let app = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if app.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(DefaultApp {
+        name: Path::new(&app).file_stem().unwrap_or_default().to_string_lossy().to_string(),
+        path: Some(app),
+    }))
+}
+
+// The association Explorer itself uses, user choice included
+#[cfg(windows)]
+pub(crate) fn default_app(path: &Path) -> Result<Option<DefaultApp>, AppError> {
+    use windows_sys::Win32::UI::Shell::{AssocQueryStringW, ASSOCF_NONE, ASSOCSTR, ASSOCSTR_EXECUTABLE, ASSOCSTR_FRIENDLYAPPNAME};
+    
+    // Folders associate through the "Folder" class, everything else by extension
+    let assoc = if path.is_dir() {
+        "Folder".to_string()
+    } else {
+        match path.extension() {
+            Some(extension) => format!(".{}", extension.to_string_lossy()),
+            None => return Ok(None),
+        }
+    };
+    let assoc: Vec<u16> = assoc.encode_utf16().chain([0]).collect();
+    let open: Vec<u16> = "open".encode_utf16().chain([0]).collect();
+    
+    let query = |kind: ASSOCSTR| {
+        let mut buffer = [0u16; 1024];
+        let mut length = buffer.len() as u32;
+        // SAFETY: both inputs are null-terminated and `length` is the buffer's size
+        let result = unsafe {
+            AssocQueryStringW(ASSOCF_NONE, kind, assoc.as_ptr(), open.as_ptr(), buffer.as_mut_ptr(), &mut length)
+        };
+        // Any failure, ERROR_NO_ASSOCIATION included, means nothing to report
+        (result == 0 && length > 1).then(|| String::from_utf16_lossy(&buffer[..length as usize - 1]))
+    };
+    
+    let executable = query(ASSOCSTR_EXECUTABLE);
+    let name = query(ASSOCSTR_FRIENDLYAPPNAME).or_else(|| {
+        executable.as_ref().map(|exe| Path::new(exe).file_stem().unwrap_or_default().to_string_lossy().to_string())
+    });
+    Ok(name.map(|name| DefaultApp { name, path: executable }))
+}
+
+// xdg-mime for the file's type and its default handler, then that handler's
+// desktop entry for a display name
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn default_app(path: &Path) -> Result<Option<DefaultApp>, AppError> {
+    let xdg_mime = |args: &[&std::ffi::OsStr]| -> Result<Option<String>, AppError> {
+        let output = Command::new("xdg-mime").args(args).output()
+            .map_err(|e| AppError::io("Failed to run xdg-mime", e))?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((output.status.success() && !value.is_empty()).then_some(value))
+    };
+    
+    let Some(mime) = xdg_mime(&["query".as_ref(), "filetype".as_ref(), path.as_os_str()])? else {
+        return Ok(None);
+    };
+    let Some(desktop_id) = xdg_mime(&["query".as_ref(), "default".as_ref(), mime.as_ref()])? else {
+        return Ok(None);
+    };
+    
+    let entry = desktop_entry_path(&desktop_id);
+    let name = entry.as_ref()
+        .and_then(|entry| std::fs::read_to_string(entry).ok())
+        .and_then(|contents| desktop_entry_value(&contents, "Name").map(str::to_string))
+        .unwrap_or_else(|| desktop_id.trim_end_matches(".desktop").to_string());
+    
+    Ok(Some(DefaultApp { name, path: entry.map(|entry| entry.to_string_lossy().to_string()) }))
+}
+
+// Looks a desktop file id up in the XDG data folders, user's first. Ids like
+// "org-gnome-Foo.desktop" may also stand for "org/gnome/Foo.desktop".
+#[cfg(all(unix, not(target_os = "macos")))]
+fn desktop_entry_path(desktop_id: &str) -> Option<std::path::PathBuf> {
+    xdg_data_dirs().into_iter()
+        .map(|dir| dir.join("applications"))
+        .flat_map(|dir| [dir.join(desktop_id), dir.join(desktop_id.replace('-', "/"))])
+        .find(|candidate| candidate.is_file())
+}
+
+// $XDG_DATA_HOME then $XDG_DATA_DIRS, with the spec's defaults
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn xdg_data_dirs() -> Vec<std::path::PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local/share")));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    
+    data_home.into_iter()
+        .chain(data_dirs.split(':').map(std::path::PathBuf::from))
+        .collect()
+}
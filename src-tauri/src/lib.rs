@@ -1,24 +1,108 @@
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
-use std::io::Read;
-use std::sync::Mutex;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::io::{Read, Seek, SeekFrom, Write};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use encoding_rs::UTF_8;
-use notify::{Watcher, RecursiveMode};
-use notify_debouncer_full::{new_debouncer, Debouncer, FileIdMap};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::Manager;
 
-#[derive(Debug, Serialize, Deserialize)]
+mod activity;
+mod archive;
+mod batch_rename;
+mod bookmarks;
+mod chunked_write;
+mod clipboard;
+mod compare;
+mod dir_size;
+mod disks;
+mod duplicates;
+mod encoding;
+mod empty_dirs;
+mod error;
+mod glob_delete;
+mod hashing;
+mod icons;
+mod identity;
+mod launch;
+mod lines;
+mod mime;
+mod name_index;
+mod operation_log;
+mod operations;
+mod paths;
+mod permissions;
+mod preview;
+mod recent;
+mod search;
+mod split;
+mod stream;
+mod tags;
+mod tail;
+mod templates;
+mod thumbnail;
+mod transfer;
+mod trash_bin;
+mod undo;
+mod watch_index;
+mod watcher;
+mod xattrs;
+
+pub use error::AppError;
+use bookmarks::BookmarkStore;
+use chunked_write::PendingWrites;
+use clipboard::ClipboardState;
+use icons::IconCache;
+use operations::OperationRegistry;
+use recent::RecentFiles;
+use tags::TagStore;
+use tail::TailState;
+use templates::TemplateStore;
+use trash_bin::TrashLedger;
+use name_index::NameIndexes;
+use operation_log::OperationLog;
+use undo::UndoJournal;
+use watcher::WatcherState;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FileItem {
     id: String,
     name: String,
-    file_type: String, // "file" or "folder"
+    file_type: String, // "file", "folder", or "broken" for a dangling symlink
     size: Option<u64>,
     date_modified: DateTime<Utc>,
     extension: Option<String>,
     path: String,
+    hidden: bool,
+    symlink_target: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct EntryCounts {
+    files: usize, // broken links count as files, as in listings
+    folders: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryPage {
+    items: Vec<FileItem>,
+    offset: usize,
+    total: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileMetadata {
+    path: String,
+    name: String,
+    file_type: String, // "file" or "folder"
+    size: u64,
+    created: Option<DateTime<Utc>>,
+    accessed: Option<DateTime<Utc>>,
+    modified: Option<DateTime<Utc>>,
+    readonly: bool,
+    is_symlink: bool,
+    permissions: Option<u32>, // Unix mode bits; None on Windows
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,71 +110,66 @@ pub struct TextFileContent {
     content: String,
     truncated: bool,
     encoding: String,
+    had_errors: bool, // true when invalid sequences were replaced while decoding
+    is_binary: bool, // content is left empty; offer `read_binary_file` instead
+    line_ending: String, // "lf", "crlf" or "mixed", to write back with `write_text_file`
+    final_newline: bool,
     size: u64,
+    // Syntax highlighting hint ("rust", "python", ...) from the name or a shebang
+    language: Option<String>,
+    compression: Option<String>, // "gzip", "zstd" or "bzip2" when the content was inflated
+    // Bytes after inflating; `size` stays the size on disk. None for a
+    // truncated read, as inflating stops at `max_bytes`.
+    decompressed_size: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct FilesystemChange {
-    kind: String,
-    paths: Vec<String>,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HexRow {
+    offset: u64,
+    bytes: Vec<u8>,
+    ascii: String, // printable ASCII as is, everything else as "."
 }
 
-// Filesystem watcher state
-type DebouncerType = Debouncer<notify::RecommendedWatcher, FileIdMap>;
-pub struct WatcherState(Mutex<Option<DebouncerType>>);
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HexDump {
+    rows: Vec<HexRow>,
+    offset: u64,
+    size: u64, // of the whole file, for the viewer's scroll extent
+}
 
-#[tauri::command]
-async fn start_watch(
-    path: String,
-    app_handle: AppHandle,
-    state: tauri::State<'_, WatcherState>,
-) -> Result<(), String> {
-    let mut watcher_lock = state.0.lock().map_err(|e| format!("Failed to lock watcher: {}", e))?;
-    
-    // Stop existing watcher if any
-    *watcher_lock = None;
-    
-    // Create new debounced watcher
-    let app_handle_clone = app_handle.clone();
-    let mut debouncer = new_debouncer(
-        Duration::from_millis(500),
-        None,
-        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
-            match result {
-                Ok(events) => {
-                    for event in events {
-                        // Convert event to serializable format
-                        let fs_change = FilesystemChange {
-                            kind: format!("{:?}", event.event.kind),
-                            paths: event.paths.iter().map(|p| p.display().to_string()).collect(),
-                        };
-                        let _ = app_handle_clone.emit("fs-change", fs_change);
-                    }
-                }
-                Err(errors) => {
-                    for error in errors {
-                        eprintln!("Filesystem watch error: {:?}", error);
-                    }
-                }
-            }
-        },
-    ).map_err(|e| format!("Failed to create watcher: {}", e))?;
-    
-    // Watch the directory
-    let watch_path = Path::new(&path);
-    debouncer.watcher().watch(watch_path, RecursiveMode::NonRecursive)
-        .map_err(|e| format!("Failed to watch directory: {}", e))?;
-    
-    *watcher_lock = Some(debouncer);
-    
-    Ok(())
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BinaryFileContent {
+    content: String, // base64
+    truncated: bool,
+    size: u64,
 }
 
-#[tauri::command]
-async fn stop_watch(state: tauri::State<'_, WatcherState>) -> Result<(), String> {
-    let mut watcher_lock = state.0.lock().map_err(|e| format!("Failed to lock watcher: {}", e))?;
-    *watcher_lock = None;
-    Ok(())
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileRange {
+    content: String,
+    offset: u64,
+    bytes_read: u64,
+    encoding: String,
+    had_errors: bool,
+    size: u64,
+}
+
+// Describes the path itself: a symlink reports is_symlink with is_dir and
+// is_file both false, whatever it points to
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PathProbe {
+    exists: bool,
+    is_dir: bool,
+    is_file: bool,
+    is_symlink: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashResult {
+    path: String,
+    trashed: bool,
+    trash_id: Option<String>, // for `restore_from_trash`, see trash_bin.rs
+    message: String,
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -100,61 +179,116 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn get_current_directory() -> Result<String, String> {
+async fn get_current_directory() -> Result<String, AppError> {
     match std::env::current_dir() {
         Ok(path) => Ok(path.to_string_lossy().to_string()),
-        Err(e) => Err(format!("Failed to get current directory: {}", e)),
+        Err(e) => Err(AppError::io("Failed to get current directory", e)),
     }
 }
 
 #[tauri::command]
-async fn list_directory(path: String) -> Result<Vec<FileItem>, String> {
-    let path = Path::new(&path);
+async fn list_directory(
+    path: String,
+    recursive: Option<bool>,
+    max_depth: Option<u32>,
+    include_hidden: Option<bool>,
+    sort_by: Option<String>,
+    sort_dir: Option<String>,
+    folders_first: Option<bool>,
+) -> Result<Vec<FileItem>, AppError> {
+    let path = &paths::extended_path(Path::new(&path));
     
     if !path.exists() {
-        return Err("Directory does not exist".to_string());
+        return Err(AppError::NotFound("Directory does not exist".to_string()));
     }
     
     if !path.is_dir() {
-        return Err("Path is not a directory".to_string());
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let include_hidden = include_hidden.unwrap_or(false);
+    let order = SortOrder::parse(sort_by.as_deref(), sort_dir.as_deref(), folders_first)?;
+    
+    if !recursive.unwrap_or(false) {
+        return read_directory_level(path, include_hidden, &order);
     }
     
+    // Flattened, depth-first listing. Each directory's children are sorted on
+    // their own and emitted right after the directory itself, so the frontend
+    // can rebuild the tree from `path` alone.
     let mut items = Vec::new();
+    let mut visited = HashSet::new();
+    if let Ok(canonical) = fs::canonicalize(path) {
+        visited.insert(canonical);
+    }
+    collect_directory_recursive(path, 1, max_depth, include_hidden, &order, &mut visited, &mut items)?;
+    
+    Ok(items)
+}
+
+fn collect_directory_recursive(
+    dir: &Path,
+    depth: u32,
+    max_depth: Option<u32>,
+    include_hidden: bool,
+    order: &SortOrder,
+    visited: &mut HashSet<PathBuf>,
+    items: &mut Vec<FileItem>,
+) -> Result<(), AppError> {
+    for mut item in read_directory_level(dir, include_hidden, order)? {
+        let is_folder = item.file_type == "folder";
+        let child_path = PathBuf::from(&item.path);
+        
+        item.id = items.len().to_string();
+        items.push(item);
+        
+        if !is_folder || max_depth.is_some_and(|max| depth >= max) {
+            continue;
+        }
+        
+        // Guard against symlink cycles: only descend into each real directory once
+        let canonical = match fs::canonicalize(&child_path) {
+            Ok(canonical) => canonical,
+            Err(_) => continue,
+        };
+        if !visited.insert(canonical) {
+            continue;
+        }
+        
+        // Unreadable subdirectories shouldn't fail the whole listing
+        let _ = collect_directory_recursive(
+            &child_path,
+            depth + 1,
+            max_depth,
+            include_hidden,
+            order,
+            visited,
+            items,
+        );
+    }
     
-    match fs::read_dir(path) {
+    Ok(())
+}
+
+fn read_directory_level(path: &Path, include_hidden: bool, order: &SortOrder) -> Result<Vec<FileItem>, AppError> {
+    let mut items = Vec::new();
+    
+    match fs::read_dir(paths::extended_path(path)) {
         Ok(entries) => {
             for (index, entry) in entries.enumerate() {
                 match entry {
                     Ok(entry) => {
                         let file_path = entry.path();
-                        let metadata = match entry.metadata() {
+                        // Don't follow symlinks here; `file_item` resolves them explicitly
+                        let metadata = match fs::symlink_metadata(&file_path) {
                             Ok(meta) => meta,
                             Err(_) => continue,
                         };
                         
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        let is_dir = metadata.is_dir();
-                        let size = if is_dir { None } else { Some(metadata.len()) };
-                        
-                        let extension = if is_dir {
-                            None
-                        } else {
-                            file_path.extension().map(|ext| ext.to_string_lossy().to_string())
-                        };
-                        
-                        let modified = metadata.modified()
-                            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                        let date_modified = DateTime::<Utc>::from(modified);
-                        
-                        let item = FileItem {
-                            id: index.to_string(),
-                            name,
-                            file_type: if is_dir { "folder".to_string() } else { "file".to_string() },
-                            size,
-                            date_modified,
-                            extension,
-                            path: file_path.to_string_lossy().to_string(),
-                        };
+                        let item = file_item(&file_path, &metadata, index.to_string());
+                        if item.hidden && !include_hidden {
+                            continue;
+                        }
                         
                         items.push(item);
                     }
@@ -162,244 +296,673 @@ async fn list_directory(path: String) -> Result<Vec<FileItem>, String> {
                 }
             }
         }
-        Err(e) => return Err(format!("Failed to read directory: {}", e)),
+        Err(e) => return Err(AppError::io("Failed to read directory", e)),
     }
     
-    // Sort items: folders first, then files, both alphabetically
+    sort_items(&mut items, order);
+    
+    Ok(items)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Name,
+    Size,
+    Modified,
+    Type,      // folder, file or broken link
+    Extension,
+}
+
+// Parsed from all-`None` options this is the original listing order: folders
+// first, then files (and broken links), both alphabetically
+#[derive(Debug, Clone, Copy)]
+struct SortOrder {
+    key: SortKey,
+    descending: bool,
+    folders_first: bool,
+}
+
+impl SortOrder {
+    fn parse(sort_by: Option<&str>, sort_dir: Option<&str>, folders_first: Option<bool>) -> Result<Self, AppError> {
+        let key = match sort_by.unwrap_or("name") {
+            "name" => SortKey::Name,
+            "size" => SortKey::Size,
+            "modified" => SortKey::Modified,
+            "type" => SortKey::Type,
+            "extension" => SortKey::Extension,
+            other => return Err(AppError::InvalidInput(format!(
+                "Unknown sort '{}', expected \"name\", \"size\", \"modified\", \"type\" or \"extension\"",
+                other
+            ))),
+        };
+        let descending = match sort_dir.unwrap_or("asc") {
+            "asc" | "ascending" => false,
+            "desc" | "descending" => true,
+            other => return Err(AppError::InvalidInput(format!(
+                "Unknown sort direction '{}', expected \"asc\" or \"desc\"",
+                other
+            ))),
+        };
+        
+        Ok(SortOrder { key, descending, folders_first: folders_first.unwrap_or(true) })
+    }
+}
+
+// Ties on the key fall back to the name and then the full path, so the order
+// is total and identical on every call. Descending flips everything but the
+// folders-first grouping.
+fn sort_items(items: &mut [FileItem], order: &SortOrder) {
     items.sort_by(|a, b| {
-        match (a.file_type.as_str(), b.file_type.as_str()) {
-            ("folder", "file") => std::cmp::Ordering::Less,
-            ("file", "folder") => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        let by_key = match order.key {
+            SortKey::Name => std::cmp::Ordering::Equal,
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Modified => a.date_modified.cmp(&b.date_modified),
+            SortKey::Type => a.file_type.cmp(&b.file_type),
+            SortKey::Extension => a.extension.as_deref().map(str::to_lowercase)
+                .cmp(&b.extension.as_deref().map(str::to_lowercase)),
+        };
+        let ordering = by_key
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.path.cmp(&b.path));
+        let ordering = if order.descending { ordering.reverse() } else { ordering };
+        
+        if order.folders_first {
+            (b.file_type == "folder").cmp(&(a.file_type == "folder")).then(ordering)
+        } else {
+            ordering
         }
     });
+}
+
+// One window of a sorted directory listing, for virtual scrolling. The folder
+// is re-read on every call, but only `limit` items cross over to the frontend.
+// Ids are positions in the full sorted listing, so they stay unique across pages.
+#[tauri::command]
+async fn list_directory_paged(
+    path: String,
+    offset: usize,
+    limit: usize,
+    sort: Option<String>,
+    sort_dir: Option<String>,
+    folders_first: Option<bool>,
+    include_hidden: Option<bool>,
+) -> Result<DirectoryPage, AppError> {
+    let path = Path::new(&path);
     
-    Ok(items)
+    if !path.is_dir() {
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let order = SortOrder::parse(sort.as_deref(), sort_dir.as_deref(), folders_first)?;
+    let items = read_directory_level(path, include_hidden.unwrap_or(false), &order)?;
+    
+    let total = items.len();
+    let items = items.into_iter()
+        .enumerate()
+        .skip(offset)
+        .take(limit)
+        .map(|(index, mut item)| {
+            item.id = index.to_string();
+            item
+        })
+        .collect();
+    
+    Ok(DirectoryPage { items, offset, total })
+}
+
+// How many files and folders `list_directory` would show, from the entry
+// types alone. Only symlinks cost a lookup each, to count links to folders
+// as folders.
+#[tauri::command]
+async fn count_directory_entries(path: String, include_hidden: Option<bool>) -> Result<EntryCounts, AppError> {
+    if !Path::new(&path).is_dir() {
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let entries = fs::read_dir(paths::extended_path(Path::new(&path)))
+        .map_err(|e| AppError::io("Failed to read directory", e))?;
+    let include_hidden = include_hidden.unwrap_or(false);
+    
+    let mut counts = EntryCounts::default();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if !include_hidden && entry_is_hidden(&entry) {
+            continue;
+        }
+        let is_dir = match entry.file_type() {
+            Ok(file_type) if file_type.is_symlink() => fs::metadata(entry.path()).is_ok_and(|meta| meta.is_dir()),
+            Ok(file_type) => file_type.is_dir(),
+            Err(_) => continue,
+        };
+        if is_dir {
+            counts.folders += 1;
+        } else {
+            counts.files += 1;
+        }
+    }
+    
+    Ok(counts)
+}
+
+// Builds a FileItem from un-followed (`symlink_metadata`) metadata. Symlinks
+// take their file_type from the target, or "broken" when the target is gone.
+fn file_item(file_path: &Path, metadata: &fs::Metadata, id: String) -> FileItem {
+    let name = file_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let hidden = is_hidden(&name, metadata);
+    
+    let (file_type, symlink_target) = if metadata.file_type().is_symlink() {
+        let target = fs::canonicalize(file_path).ok().or_else(|| fs::read_link(file_path).ok());
+        let file_type = match fs::metadata(file_path) {
+            Ok(target_meta) if target_meta.is_dir() => "folder",
+            Ok(_) => "file",
+            Err(_) => "broken",
+        };
+        (file_type, target.map(|t| paths::display_path(&t)))
+    } else if metadata.is_dir() {
+        ("folder", None)
+    } else {
+        ("file", None)
+    };
+    
+    let is_dir = file_type == "folder";
+    let size = if is_dir { None } else { Some(metadata.len()) };
+    
+    let extension = if is_dir {
+        None
+    } else {
+        file_path.extension().map(|ext| ext.to_string_lossy().to_string())
+    };
+    
+    let modified = metadata.modified()
+        .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    let date_modified = DateTime::<Utc>::from(modified);
+    
+    FileItem {
+        id,
+        name,
+        file_type: file_type.to_string(),
+        size,
+        date_modified,
+        extension,
+        path: paths::display_path(file_path),
+        hidden,
+        symlink_target,
+    }
+}
+
+// Dotfiles are hidden on Unix; Windows uses the FILE_ATTRIBUTE_HIDDEN flag instead
+#[cfg(not(windows))]
+fn is_hidden(name: &str, _metadata: &fs::Metadata) -> bool {
+    name.starts_with('.')
+}
+
+#[cfg(windows)]
+fn is_hidden(_name: &str, metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+}
+
+// The same test without a stat on Unix; on Windows the directory scan
+// already has the attributes
+#[cfg(not(windows))]
+fn entry_is_hidden(entry: &fs::DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+#[cfg(windows)]
+fn entry_is_hidden(entry: &fs::DirEntry) -> bool {
+    entry.metadata().is_ok_and(|metadata| is_hidden("", &metadata))
 }
 
 #[tauri::command]
-async fn navigate_to_path(path: String) -> Result<Vec<FileItem>, String> {
+async fn navigate_to_path(path: String) -> Result<Vec<FileItem>, AppError> {
     let path = Path::new(&path);
     
     if !path.exists() {
-        return Err("Path does not exist".to_string());
+        return Err(AppError::NotFound("Path does not exist".to_string()));
     }
     
     if path.is_file() {
         // If it's a file, navigate to its parent directory
         if let Some(parent) = path.parent() {
-            return list_directory(parent.to_string_lossy().to_string()).await;
+            return list_directory(parent.to_string_lossy().to_string(), None, None, None, None, None, None).await;
         } else {
-            return Err("Cannot navigate to file without parent directory".to_string());
+            return Err(AppError::InvalidInput("Cannot navigate to file without parent directory".to_string()));
         }
     }
     
-    list_directory(path.to_string_lossy().to_string()).await
+    list_directory(path.to_string_lossy().to_string(), None, None, None, None, None, None).await
 }
 
 #[tauri::command]
-async fn get_home_directory() -> Result<String, String> {
+async fn probe_path(path: String) -> Result<PathProbe, AppError> {
+    match fs::symlink_metadata(&path) {
+        Ok(metadata) => Ok(PathProbe {
+            exists: true,
+            is_dir: metadata.is_dir(),
+            is_file: metadata.is_file(),
+            is_symlink: metadata.file_type().is_symlink(),
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(PathProbe {
+            exists: false,
+            is_dir: false,
+            is_file: false,
+            is_symlink: false,
+        }),
+        Err(e) => Err(AppError::io("Failed to probe path", e)),
+    }
+}
+
+// Upper bound on one `stat_many` call
+const MAX_STAT_PATHS: usize = 10_000;
+const STAT_THREADS: usize = 8;
+
+// The FileItem for each path, in order, or None where it's missing or can't
+// be read. Ids are the paths. Looked up on a few threads at once, which
+// mostly pays off on network drives.
+#[tauri::command]
+async fn stat_many(paths: Vec<String>) -> Result<Vec<Option<FileItem>>, AppError> {
+    if paths.len() > MAX_STAT_PATHS {
+        return Err(AppError::InvalidInput(format!("Too many paths (max {})", MAX_STAT_PATHS)));
+    }
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let stat = |path: &String| {
+            let item_path = paths::extended_path(Path::new(path));
+            fs::symlink_metadata(&item_path).ok().map(|metadata| file_item(&item_path, &metadata, path.clone()))
+        };
+        if paths.len() < 2 * STAT_THREADS {
+            return paths.iter().map(stat).collect();
+        }
+        
+        let chunk_size = paths.len().div_ceil(STAT_THREADS);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = paths.chunks(chunk_size)
+                .map(|chunk| (chunk.len(), scope.spawn(move || chunk.iter().map(stat).collect::<Vec<_>>())))
+                .collect();
+            // A thread that died still accounts for its share, keeping the order
+            handles.into_iter()
+                .flat_map(|(len, handle)| handle.join().unwrap_or_else(|_| (0..len).map(|_| None).collect()))
+                .collect()
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Stat task failed: {}", e)))
+}
+
+#[tauri::command]
+async fn get_file_metadata(path: String) -> Result<FileMetadata, AppError> {
+    let item_path = Path::new(&path);
+    
+    let link_metadata = fs::symlink_metadata(item_path)
+        .map_err(|e| AppError::io("Failed to read file metadata", e))?;
+    let is_symlink = link_metadata.file_type().is_symlink();
+    
+    // Describe what a symlink points to, falling back to the link itself when broken
+    let metadata = if is_symlink {
+        fs::metadata(item_path).unwrap_or(link_metadata)
+    } else {
+        link_metadata
+    };
+    
+    let is_dir = metadata.is_dir();
+    
+    Ok(FileMetadata {
+        path: item_path.to_string_lossy().to_string(),
+        name: item_path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        file_type: if is_dir { "folder".to_string() } else { "file".to_string() },
+        size: metadata.len(),
+        // Not every platform/filesystem records all timestamps (notably `created`)
+        created: metadata.created().ok().map(DateTime::<Utc>::from),
+        accessed: metadata.accessed().ok().map(DateTime::<Utc>::from),
+        modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        readonly: metadata.permissions().readonly(),
+        is_symlink,
+        permissions: permission_mode(&metadata),
+    })
+}
+
+// Sets either timestamp, leaving the one that's None as it is. Works on
+// folders too. Returns the metadata as it reads back afterwards, since some
+// filesystems round timestamps (FAT to two seconds, for one).
+#[tauri::command]
+async fn set_file_times(
+    path: String,
+    modified: Option<DateTime<Utc>>,
+    accessed: Option<DateTime<Utc>>,
+) -> Result<FileMetadata, AppError> {
+    let item_path = Path::new(&path);
+    
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    let to_file_time = |time: DateTime<Utc>| filetime::FileTime::from_system_time(time.into());
+    let result = match (modified.map(to_file_time), accessed.map(to_file_time)) {
+        (Some(modified), Some(accessed)) => filetime::set_file_times(item_path, accessed, modified),
+        (Some(modified), None) => filetime::set_file_mtime(item_path, modified),
+        (None, Some(accessed)) => filetime::set_file_atime(item_path, accessed),
+        (None, None) => Ok(()),
+    };
+    result.map_err(|e| AppError::io("Failed to set file times", e))?;
+    
+    get_file_metadata(path).await
+}
+
+#[cfg(unix)]
+fn permission_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn permission_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+#[tauri::command]
+async fn get_home_directory() -> Result<String, AppError> {
     match dirs::home_dir() {
         Some(path) => Ok(path.to_string_lossy().to_string()),
-        None => Err("Unable to determine home directory".to_string()),
+        None => Err(AppError::NotFound("Unable to determine home directory".to_string())),
     }
 }
 
 #[tauri::command]
-async fn create_folder(path: String, name: String) -> Result<String, String> {
+async fn create_folder(path: String, name: String, log: tauri::State<'_, OperationLog>) -> Result<FileItem, AppError> {
+    paths::check_name(&name, Some(Path::new(&path)))?;
     let folder_path = Path::new(&path).join(&name);
     
     if folder_path.exists() {
-        return Err("Folder already exists".to_string());
+        return Err(AppError::AlreadyExists("Folder already exists".to_string()));
     }
     
-    match fs::create_dir(&folder_path) {
-        Ok(_) => Ok(format!("Folder '{}' created successfully", name)),
-        Err(e) => Err(format!("Failed to create folder: {}", e)),
-    }
+    let result = fs::create_dir(&folder_path).map_err(|e| AppError::io("Failed to create folder", e));
+    log.record_result("create", &folder_path, None, &result);
+    result?;
+    created_item(&folder_path)
+}
+
+// The FileItem for something just created, so the frontend can add it to the
+// view without re-listing. Its id is the path, unique outside of any listing.
+fn created_item(path: &Path) -> Result<FileItem, AppError> {
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|e| AppError::io("Failed to read created item", e))?;
+    Ok(file_item(path, &metadata, path.to_string_lossy().to_string()))
 }
 
 #[tauri::command]
-async fn delete_item(path: String) -> Result<String, String> {
+async fn delete_item(
+    path: String,
+    dry_run: Option<bool>,
+    expected_count: Option<u64>,
+    tags: tauri::State<'_, TagStore>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<String, AppError> {
     let item_path = Path::new(&path);
     
     if !item_path.exists() {
-        return Err("Item does not exist".to_string());
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    // A guard for scripted deletes: the item plus everything beneath it must
+    // add up to `expected_count`, so a full folder can't pass for an empty one
+    let dry_run = dry_run.unwrap_or(false);
+    let count = (dry_run || expected_count.is_some()).then(|| count_entries(item_path));
+    if let (Some(expected), Some(count)) = (expected_count, count) {
+        if expected != count {
+            return Err(AppError::InvalidInput(format!(
+                "Refusing to delete: expected {} items but found {}",
+                expected, count
+            )));
+        }
     }
     
+    // Same checks as a real delete, then report what would go
+    if let Some(count) = count.filter(|_| dry_run) {
+        return Ok(format!("Would delete {} items", count));
+    }
+    
+    let canonical = fs::canonicalize(item_path).ok();
     let result = if item_path.is_dir() {
-        fs::remove_dir_all(&item_path)
+        fs::remove_dir_all(item_path)
     } else {
-        fs::remove_file(&item_path)
+        fs::remove_file(item_path)
+    };
+    log.record("delete", item_path, None, result.as_ref().err().map(|e| e.to_string()));
+    
+    match result {
+        Ok(_) => {
+            if let Some(canonical) = canonical {
+                tags.forget_path(&canonical);
+            }
+            Ok("Item deleted successfully".to_string())
+        }
+        Err(e) => Err(AppError::io("Failed to delete item", e)),
+    }
+}
+
+// The item itself plus everything beneath it; symlinks are not followed
+fn count_entries(path: &Path) -> u64 {
+    let is_dir = fs::symlink_metadata(path).map(|meta| meta.is_dir()).unwrap_or(false);
+    if !is_dir {
+        return 1;
+    }
+    
+    let children: u64 = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| count_entries(&entry.path()))
+            .sum(),
+        Err(_) => 0,
     };
+    1 + children
+}
+
+#[tauri::command]
+async fn trash_item(
+    path: String,
+    ledger: tauri::State<'_, TrashLedger>,
+    journal: tauri::State<'_, UndoJournal>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<TrashResult, AppError> {
+    let item_path = Path::new(&path);
+    
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    let original = fs::canonicalize(item_path)
+        .map_err(|e| AppError::io("Failed to resolve path", e))?;
     
+    // A failed trash is reported in the result rather than as an error so the
+    // frontend can offer a permanent `delete_item` as the fallback
+    let result = trash_bin::trash_one(&original, &ledger);
+    log.record("trash", &original, None, result.as_ref().err().cloned());
     match result {
-        Ok(_) => Ok("Item deleted successfully".to_string()),
-        Err(e) => Err(format!("Failed to delete item: {}", e)),
+        Ok(trash_id) => {
+            journal.record_trash(format!("Trash {}", path), vec![(original, trash_id.clone())]);
+            Ok(TrashResult {
+                path,
+                trashed: true,
+                trash_id,
+                message: "Item moved to trash".to_string(),
+            })
+        }
+        Err(message) => Ok(TrashResult {
+            path,
+            trashed: false,
+            trash_id: None,
+            message,
+        }),
+    }
+}
+
+fn describe_trash_error(error: &trash::Error) -> String {
+    match error {
+        trash::Error::TargetedRoot => "Cannot move a root directory to the trash".to_string(),
+        trash::Error::CouldNotAccess { target } => {
+            format!("Could not access '{}' to move it to the trash", target)
+        }
+        _ => format!("This location does not support the trash: {}", error),
     }
 }
 
 #[tauri::command]
-async fn rename_item(old_path: String, new_name: String) -> Result<String, String> {
+async fn rename_item(
+    old_path: String,
+    new_name: String,
+    tags: tauri::State<'_, TagStore>,
+    journal: tauri::State<'_, UndoJournal>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<String, AppError> {
     let old_path = Path::new(&old_path);
     
     if !old_path.exists() {
-        return Err("Item does not exist".to_string());
+        return Err(AppError::NotFound("Item does not exist".to_string()));
     }
     
     let parent = match old_path.parent() {
         Some(parent) => parent,
-        None => return Err("Cannot rename root directory".to_string()),
+        None => return Err(AppError::InvalidInput("Cannot rename root directory".to_string())),
     };
     
+    paths::check_name(&new_name, Some(parent))?;
     let new_path = parent.join(&new_name);
     
     if new_path.exists() {
-        return Err("An item with that name already exists".to_string());
+        return Err(AppError::AlreadyExists("An item with that name already exists".to_string()));
     }
     
-    match fs::rename(&old_path, &new_path) {
-        Ok(_) => Ok(format!("Item renamed to '{}' successfully", new_name)),
-        Err(e) => Err(format!("Failed to rename item: {}", e)),
+    let canonical = fs::canonicalize(old_path).ok();
+    let result = fs::rename(old_path, &new_path);
+    log.record("rename", old_path, Some(&new_path), result.as_ref().err().map(|e| e.to_string()));
+    match result {
+        Ok(_) => {
+            tags.move_path(canonical.as_deref(), &new_path);
+            journal.record_moves(
+                format!("Rename {} to {}", old_path.display(), new_name),
+                vec![(old_path.to_path_buf(), new_path)],
+            );
+            Ok(format!("Item renamed to '{}' successfully", new_name))
+        }
+        Err(e) => Err(AppError::io("Failed to rename item", e)),
     }
 }
 
+// With `content`, the file appears with it already in place, never empty or
+// half written
 #[tauri::command]
-async fn create_file(path: String, name: String) -> Result<String, String> {
+async fn create_file(
+    path: String,
+    name: String,
+    content: Option<String>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<FileItem, AppError> {
     let base_path = Path::new(&path);
     
     if !base_path.exists() || !base_path.is_dir() {
-        return Err("Directory does not exist".to_string());
+        return Err(AppError::NotFound("Directory does not exist".to_string()));
     }
     
+    paths::check_name(&name, Some(base_path))?;
     let file_path = base_path.join(&name);
     
     if file_path.exists() {
-        return Err("A file with that name already exists".to_string());
+        return Err(AppError::AlreadyExists("A file with that name already exists".to_string()));
     }
     
-    match fs::File::create(&file_path) {
-        Ok(_) => Ok(format!("File '{}' created successfully", name)),
-        Err(e) => Err(format!("Failed to create file: {}", e)),
+    let result = match content {
+        Some(content) => write_atomic(&file_path, content.as_bytes()),
+        None => fs::File::create(&file_path).map(|_| ()),
     }
+    .map_err(|e| AppError::io("Failed to create file", e));
+    log.record_result("create", &file_path, None, &result);
+    result?;
+    created_item(&file_path)
 }
 
+// `target` is stored as given, so a relative target stays relative to the
+// link's folder. Windows needs to know whether the target is a file or a
+// folder; `symlink_type` ("file" or "dir") says so when the target doesn't
+// exist yet, and is otherwise worked out from the target.
 #[tauri::command]
-async fn copy_items(source_paths: Vec<String>, destination_path: String) -> Result<String, String> {
-    let dest_path = Path::new(&destination_path);
+async fn create_symlink(
+    target: String,
+    link_path: String,
+    symlink_type: Option<String>,
+) -> Result<FileItem, AppError> {
+    let link = Path::new(&link_path);
     
-    if !dest_path.exists() || !dest_path.is_dir() {
-        return Err("Destination directory does not exist".to_string());
+    // symlink_metadata so a broken link counts as existing too
+    if fs::symlink_metadata(link).is_ok() {
+        return Err(AppError::AlreadyExists("Something already exists at the link path".to_string()));
     }
     
-    let mut copied_count = 0;
-    
-    for source_path in source_paths {
-        let source = Path::new(&source_path);
-        
-        if !source.exists() {
-            continue; // Skip non-existent files
-        }
-        
-        let file_name = match source.file_name() {
-            Some(name) => name,
-            None => continue,
-        };
-        
-        let destination = dest_path.join(file_name);
-        
-        // Skip if destination already exists
-        if destination.exists() {
-            continue;
-        }
-        
-        let result = if source.is_dir() {
-            copy_dir_recursive(&source, &destination)
-        } else {
-            fs::copy(&source, &destination).map(|_| ())
-        };
-        
-        match result {
-            Ok(_) => copied_count += 1,
-            Err(_) => continue, // Skip failed copies
+    let target_path = Path::new(&target);
+    let is_dir = match symlink_type.as_deref() {
+        Some("file") => false,
+        Some("dir") => true,
+        Some(other) => {
+            return Err(AppError::InvalidInput(format!(
+                "Unknown symlink type '{}', expected \"file\" or \"dir\"",
+                other
+            )))
         }
-    }
+        None => link.parent().unwrap_or(Path::new("")).join(target_path).is_dir(),
+    };
     
-    Ok(format!("{} item(s) copied successfully", copied_count))
+    make_symlink(target_path, link, is_dir)?;
+    created_item(link)
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
-    fs::create_dir_all(dst)?;
-    
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
-        
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)?;
-        }
-    }
-    
-    Ok(())
+#[cfg(unix)]
+fn make_symlink(target: &Path, link: &Path, _is_dir: bool) -> Result<(), AppError> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| AppError::io("Failed to create symlink", e))
 }
 
-#[tauri::command]
-async fn move_items(source_paths: Vec<String>, destination_path: String) -> Result<String, String> {
-    let dest_path = Path::new(&destination_path);
-    
-    if !dest_path.exists() || !dest_path.is_dir() {
-        return Err("Destination directory does not exist".to_string());
-    }
-    
-    let mut moved_count = 0;
-    
-    for source_path in source_paths {
-        let source = Path::new(&source_path);
-        
-        if !source.exists() {
-            continue; // Skip non-existent files
-        }
-        
-        let file_name = match source.file_name() {
-            Some(name) => name,
-            None => continue,
-        };
-        
-        let destination = dest_path.join(file_name);
-        
-        // Skip if destination already exists
-        if destination.exists() {
-            continue;
-        }
-        
-        match fs::rename(&source, &destination) {
-            Ok(_) => moved_count += 1,
-            Err(_) => continue, // Skip failed moves
-        }
-    }
+#[cfg(windows)]
+fn make_symlink(target: &Path, link: &Path, is_dir: bool) -> Result<(), AppError> {
+    use std::os::windows::fs::{symlink_dir, symlink_file};
+    const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
     
-    Ok(format!("{} item(s) moved successfully", moved_count))
+    let result = if is_dir { symlink_dir(target, link) } else { symlink_file(target, link) };
+    result.map_err(|e| match e.raw_os_error() {
+        Some(ERROR_PRIVILEGE_NOT_HELD) => AppError::PermissionDenied(
+            "Creating symlinks needs Developer Mode enabled in Windows settings, or running as administrator"
+                .to_string(),
+        ),
+        _ => AppError::io("Failed to create symlink", e),
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn make_symlink(_target: &Path, _link: &Path, _is_dir: bool) -> Result<(), AppError> {
+    Err(AppError::Unsupported("Symlinks are not supported on this platform".to_string()))
 }
 
 #[tauri::command]
-async fn open_file_with_default_app(file_path: String) -> Result<String, String> {
+async fn open_file_with_default_app(
+    file_path: String,
+    recent: tauri::State<'_, RecentFiles>,
+) -> Result<String, AppError> {
     let path = Path::new(&file_path);
     
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(AppError::NotFound("File does not exist".to_string()));
     }
     
     if path.is_dir() {
-        return Err("Cannot open directory with default app. Use navigate instead.".to_string());
+        return Err(AppError::IsADirectory("Cannot open directory with default app. Use navigate instead.".to_string()));
     }
     
     // Use the system's default application to open the file
     match open::that(&file_path) {
-        Ok(_) => Ok(format!("Opened '{}' with default application", path.file_name().unwrap_or_default().to_string_lossy())),
-        Err(e) => Err(format!("Failed to open file: {}", e)),
+        Ok(_) => {
+            recent.record(path);
+            Ok(format!("Opened '{}' with default application", path.file_name().unwrap_or_default().to_string_lossy()))
+        }
+        Err(e) => Err(AppError::io("Failed to open file", e)),
     }
 }
 
@@ -407,32 +970,109 @@ async fn open_file_with_default_app(file_path: String) -> Result<String, String>
 async fn read_text_file(
     file_path: String,
     max_bytes: Option<u64>,
-) -> Result<TextFileContent, String> {
-    let path = Path::new(&file_path);
+    encoding: Option<String>,
+    decompress: Option<bool>,
+    recent: tauri::State<'_, RecentFiles>,
+) -> Result<TextFileContent, AppError> {
+    let path = &paths::extended_path(Path::new(&file_path));
     
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(AppError::NotFound("File does not exist".to_string()));
     }
     
     if path.is_dir() {
-        return Err("Cannot read directory as text file".to_string());
+        return Err(AppError::IsADirectory("Cannot read directory as text file".to_string()));
     }
     
     // Get file metadata
-    let metadata = match fs::metadata(&path) {
+    let metadata = match fs::metadata(path) {
         Ok(meta) => meta,
-        Err(e) => return Err(format!("Failed to read file metadata: {}", e)),
+        Err(e) => return Err(AppError::io("Failed to read file metadata", e)),
     };
     
     let file_size = metadata.len();
     let max_bytes = max_bytes.unwrap_or(4 * 1024 * 1024); // Default 4MB
     
     // Open file and read bytes
-    let mut file = match fs::File::open(&path) {
+    let mut file = match fs::File::open(path) {
         Ok(f) => f,
-        Err(e) => return Err(format!("Failed to open file: {}", e)),
+        Err(e) => return Err(AppError::io("Failed to open file", e)),
+    };
+    
+    // Compressed files are found by their magic bytes, unless `decompress`
+    // says otherwise; `max_bytes` then limits the inflated output, so a small
+    // file that expands hugely is only read as far as needed
+    let compression = match decompress {
+        Some(false) => None,
+        _ => {
+            let mut header = [0u8; 4];
+            let read = file.read(&mut header).map_err(|e| AppError::io("Failed to read file", e))?;
+            file.seek(SeekFrom::Start(0)).map_err(|e| AppError::io("Failed to read file", e))?;
+            compression_format(&header[..read])
+        }
+    };
+    if decompress == Some(true) && compression.is_none() {
+        return Err(AppError::InvalidInput("The file is not gzip, zstd or bzip2 compressed".to_string()));
+    }
+    
+    let (buffer, truncated) = match compression {
+        Some("gzip") => read_inflated(flate2::read::MultiGzDecoder::new(file), max_bytes)?,
+        Some("zstd") => {
+            let decoder = zstd::Decoder::new(file).map_err(|e| AppError::io("Failed to decompress file", e))?;
+            read_inflated(decoder, max_bytes)?
+        }
+        Some(_) => read_inflated(bzip2::read::MultiBzDecoder::new(file), max_bytes)?,
+        None => (read_plain(file, file_size, max_bytes)?, file_size > max_bytes),
     };
+    let decompressed_size = compression.filter(|_| !truncated).map(|_| buffer.len() as u64);
+    
+    recent.record(Path::new(&file_path));
+    
+    // Decoding binary data only produces a screen of replacement characters.
+    // An explicit encoding skips the check, to force a file open as text.
+    if encoding.is_none() && encoding::looks_binary(&buffer) {
+        return Ok(TextFileContent {
+            content: String::new(),
+            truncated,
+            encoding: String::new(),
+            had_errors: false,
+            is_binary: true,
+            line_ending: "lf".to_string(),
+            final_newline: false,
+            size: file_size,
+            language: None,
+            compression: compression.map(str::to_string),
+            decompressed_size,
+        });
+    }
     
+    // Detect encoding (unless one was given) and decode
+    let decoded = encoding::decode_text(&buffer, encoding.as_deref())?;
+    let (line_ending, final_newline) = encoding::line_endings(&decoded.content);
+    // "data.json.gz" is highlighted as JSON
+    let name_path = match compression {
+        Some(_) => Path::new(&file_path).with_extension(""),
+        None => PathBuf::from(&file_path),
+    };
+    let language = mime::language_hint(&name_path, &decoded.content).map(str::to_string);
+    
+    Ok(TextFileContent {
+        content: decoded.content,
+        truncated,
+        encoding: decoded.encoding.name().to_string(),
+        had_errors: decoded.had_errors,
+        is_binary: false,
+        line_ending: line_ending.to_string(),
+        // Of a truncated read, this describes where the read stopped
+        final_newline: final_newline && !truncated,
+        size: file_size,
+        language,
+        compression: compression.map(str::to_string),
+        decompressed_size,
+    })
+}
+
+fn read_plain(mut file: fs::File, file_size: u64, max_bytes: u64) -> Result<Vec<u8>, AppError> {
     let bytes_to_read = std::cmp::min(file_size, max_bytes);
     let mut buffer = vec![0u8; bytes_to_read as usize];
     
@@ -444,51 +1084,347 @@ async fn read_text_file(
             let mut limited_file = file.take(max_bytes);
             match limited_file.read_to_end(&mut buffer) {
                 Ok(_) => {},
-                Err(e) => return Err(format!("Failed to read file: {}", e)),
+                Err(e) => return Err(AppError::io("Failed to read file", e)),
             }
         }
     };
     
-    // Detect encoding and decode
-    let (decoded_content, encoding_used, _had_errors) = UTF_8.decode(&buffer);
+    Ok(buffer)
+}
+
+// Inflates at most `max_bytes`, plus one byte to tell whether there was more
+fn read_inflated(decoder: impl Read, max_bytes: u64) -> Result<(Vec<u8>, bool), AppError> {
+    let mut buffer = Vec::new();
+    decoder.take(max_bytes.saturating_add(1))
+        .read_to_end(&mut buffer)
+        .map_err(|e| AppError::io("Failed to decompress file", e))?;
+    let truncated = buffer.len() as u64 > max_bytes;
+    buffer.truncate(max_bytes as usize);
+    Ok((buffer, truncated))
+}
+
+// Compressed formats by magic bytes
+fn compression_format(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(&[0x1f, 0x8b]) {
+        Some("gzip")
+    } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Some("zstd")
+    } else if header.starts_with(b"BZh") {
+        Some("bzip2")
+    } else {
+        None
+    }
+}
+
+// Largest window a single read_file_range or read_binary_file call may return
+const MAX_RANGE_BYTES: u64 = 16 * 1024 * 1024;
+
+#[tauri::command]
+async fn read_binary_file(path: String, max_bytes: Option<u64>) -> Result<BinaryFileContent, AppError> {
+    let file_path = Path::new(&path);
+    
+    if file_path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot read directory as binary file".to_string()));
+    }
+    
+    let max_bytes = max_bytes.unwrap_or(4 * 1024 * 1024); // Default 4MB
+    if max_bytes > MAX_RANGE_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "max_bytes is too large (max {} bytes)",
+            MAX_RANGE_BYTES
+        )));
+    }
+    
+    let file = fs::File::open(file_path).map_err(|e| AppError::io("Failed to open file", e))?;
+    let size = file.metadata()
+        .map_err(|e| AppError::io("Failed to read file metadata", e))?
+        .len();
+    
+    let mut buffer = Vec::new();
+    file.take(max_bytes).read_to_end(&mut buffer)
+        .map_err(|e| AppError::io("Failed to read file", e))?;
+    
+    Ok(BinaryFileContent {
+        content: STANDARD.encode(&buffer),
+        truncated: size > buffer.len() as u64,
+        size,
+    })
+}
+
+const HEX_ROW_BYTES: usize = 16;
+// Each byte costs several characters of JSON, so pages stay small
+const MAX_HEX_BYTES: u64 = 64 * 1024;
+
+// Rows of 16 bytes starting at `offset`; page through a large file by moving
+// the offset. Reading at or past EOF gives no rows.
+#[tauri::command]
+async fn read_hex(path: String, offset: u64, length: u64) -> Result<HexDump, AppError> {
+    let file_path = Path::new(&path);
+    
+    if file_path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot read directory as binary file".to_string()));
+    }
+    
+    if length > MAX_HEX_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "Requested range is too large (max {} bytes)",
+            MAX_HEX_BYTES
+        )));
+    }
+    
+    let mut file = fs::File::open(file_path).map_err(|e| AppError::io("Failed to open file", e))?;
+    let size = file.metadata()
+        .map_err(|e| AppError::io("Failed to read file metadata", e))?
+        .len();
+    
+    let mut buffer = Vec::new();
+    if offset < size {
+        file.seek(SeekFrom::Start(offset)).map_err(|e| AppError::io("Failed to seek file", e))?;
+        file.take(length).read_to_end(&mut buffer)
+            .map_err(|e| AppError::io("Failed to read file", e))?;
+    }
+    
+    let rows = buffer.chunks(HEX_ROW_BYTES)
+        .enumerate()
+        .map(|(index, bytes)| HexRow {
+            offset: offset + (index * HEX_ROW_BYTES) as u64,
+            bytes: bytes.to_vec(),
+            ascii: bytes.iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect(),
+        })
+        .collect();
+    
+    Ok(HexDump { rows, offset, size })
+}
+
+#[tauri::command]
+async fn read_file_range(
+    path: String,
+    offset: u64,
+    length: u64,
+    encoding: Option<String>,
+) -> Result<FileRange, AppError> {
+    let file_path = Path::new(&path);
+    
+    if !file_path.exists() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    
+    if file_path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot read directory as text file".to_string()));
+    }
+    
+    if length > MAX_RANGE_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "Requested range is too large (max {} bytes)",
+            MAX_RANGE_BYTES
+        )));
+    }
+    
+    let mut file = match fs::File::open(file_path) {
+        Ok(f) => f,
+        Err(e) => return Err(AppError::io("Failed to open file", e)),
+    };
+    
+    let size = match file.metadata() {
+        Ok(meta) => meta.len(),
+        Err(e) => return Err(AppError::io("Failed to read file metadata", e)),
+    };
     
-    let truncated = file_size > max_bytes;
+    // Reading at or past EOF just yields an empty (or short) buffer
+    let mut buffer = Vec::new();
+    if offset < size {
+        if let Err(e) = file.seek(SeekFrom::Start(offset)) {
+            return Err(AppError::io("Failed to seek file", e));
+        }
+        if let Err(e) = file.take(length).read_to_end(&mut buffer) {
+            return Err(AppError::io("Failed to read file", e));
+        }
+    }
     
-    Ok(TextFileContent {
-        content: decoded_content.to_string(),
-        truncated,
-        encoding: encoding_used.name().to_string(),
-        size: file_size,
+    let decoded = encoding::decode_text(&buffer, encoding.as_deref())?;
+    
+    Ok(FileRange {
+        content: decoded.content,
+        offset,
+        bytes_read: buffer.len() as u64,
+        encoding: decoded.encoding.name().to_string(),
+        had_errors: decoded.had_errors,
+        size,
     })
 }
 
+// `line_ending` ("lf" or "crlf") rewrites every line break in `content`
+// first, so a file read as CRLF can be saved back as CRLF
 #[tauri::command]
 async fn write_text_file(
     file_path: String,
     content: String,
-) -> Result<String, String> {
-    let path = Path::new(&file_path);
+    append: Option<bool>,
+    create: Option<bool>,
+    atomic: Option<bool>,
+    line_ending: Option<String>,
+) -> Result<String, AppError> {
+    let path = &paths::extended_path(Path::new(&file_path));
+    let create = create.unwrap_or(false);
+    let append = append.unwrap_or(false);
+    let content = match line_ending.as_deref() {
+        Some(style) => encoding::convert_line_endings(&content, style)?,
+        None => content,
+    };
     
-    if !path.exists() {
-        return Err("File does not exist".to_string());
+    if !path.exists() && !create {
+        return Err(AppError::NotFound("File does not exist".to_string()));
     }
     
     if path.is_dir() {
-        return Err("Cannot write to directory".to_string());
+        return Err(AppError::IsADirectory("Cannot write to directory".to_string()));
+    }
+    
+    // Overwrites are atomic by default; appends always happen in place
+    if !append && atomic.unwrap_or(true) {
+        return match write_atomic(path, content.as_bytes()) {
+            Ok(_) => Ok("File saved successfully".to_string()),
+            Err(e) => Err(AppError::io("Failed to write file", e)),
+        };
+    }
+    
+    let mut options = fs::OpenOptions::new();
+    if append {
+        options.append(true);
+    } else {
+        options.write(true).truncate(true);
     }
+    options.create(create);
+    
+    let mut file = match options.open(path) {
+        Ok(f) => f,
+        Err(e) => return Err(AppError::io("Failed to open file", e)),
+    };
     
-    match fs::write(&path, content.as_bytes()) {
+    match file.write_all(content.as_bytes()) {
         Ok(_) => Ok("File saved successfully".to_string()),
-        Err(e) => Err(format!("Failed to write file: {}", e)),
+        Err(e) => Err(AppError::io("Failed to write file", e)),
+    }
+}
+
+// Replaces an existing file, keeping the old content next to it as
+// `name + backup_suffix` (".bak" by default), or `name.bak.1`, `name.bak.2`
+// and so on when that's taken. The new content is fully written before the
+// old file is moved aside. Returns the backup's path.
+#[tauri::command]
+async fn write_text_file_with_backup(
+    file_path: String,
+    content: String,
+    backup_suffix: Option<String>,
+) -> Result<String, AppError> {
+    let suffix = backup_suffix.unwrap_or_else(|| ".bak".to_string());
+    if suffix.is_empty() || suffix.contains(['/', '\\']) {
+        return Err(AppError::InvalidInput("Backup suffix must be non-empty and contain no path separators".to_string()));
     }
+    
+    let path = Path::new(&file_path);
+    if path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot write to directory".to_string()));
+    }
+    // Back up the file a symlink points to, leaving the link in place
+    let path = fs::canonicalize(path).map_err(|e| AppError::io("Failed to resolve file", e))?;
+    
+    let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let backup_path = (0u32..)
+        .map(|n| match n {
+            0 => path.with_file_name(format!("{}{}", name, suffix)),
+            n => path.with_file_name(format!("{}{}.{}", name, suffix, n)),
+        })
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .ok_or_else(|| AppError::Internal("Ran out of backup names".to_string()))?;
+    
+    let temp_path = temp_sibling_path(&path);
+    let result = (|| {
+        let mut temp_file = fs::File::create(&temp_path)?;
+        temp_file.write_all(content.as_bytes())?;
+        temp_file.sync_all()?;
+        fs::set_permissions(&temp_path, fs::metadata(&path)?.permissions())?;
+        
+        fs::rename(&path, &backup_path)?;
+        if let Err(e) = fs::rename(&temp_path, &path) {
+            // Put the original back rather than leave nothing at the path
+            let _ = fs::rename(&backup_path, &path);
+            return Err(e);
+        }
+        Ok(())
+    })();
+    
+    match result {
+        Ok(()) => Ok(backup_path.to_string_lossy().to_string()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(AppError::io("Failed to write file", e))
+        }
+    }
+}
+
+// Writes to a temporary sibling and renames it over the target, so a crash
+// mid-write leaves either the old or the new content, never a truncated file.
+// The rename is only atomic because the temp file is on the same filesystem.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), std::io::Error> {
+    // Resolve symlinks so the link itself isn't replaced by a regular file
+    let resolved = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let path = resolved.as_path();
+    let temp_path = temp_sibling_path(path);
+    
+    let result = (|| {
+        let mut temp_file = fs::File::create(&temp_path)?;
+        temp_file.write_all(bytes)?;
+        temp_file.sync_all()?;
+        
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        
+        fs::rename(&temp_path, path)
+    })();
+    
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    
+    result
+}
+
+fn temp_sibling_path(path: &Path) -> PathBuf {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let unique = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(".{}.{}-{}.tmp", name, std::process::id(), unique))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(WatcherState(Mutex::new(None)))
+        .manage(WatcherState::default())
+        .manage(OperationRegistry::default())
+        .manage(TrashLedger::default())
+        .manage(ClipboardState::default())
+        .manage(UndoJournal::default())
+        .manage(PendingWrites::default())
+        .manage(TailState::default())
+        .manage(IconCache::default())
+        .manage(OperationLog::default())
+        .manage(NameIndexes::default())
         .setup(|app| {
+            let tag_file = app.path().app_data_dir().ok().map(|dir| dir.join("tags.json"));
+            app.manage(TagStore::open(tag_file));
+            let bookmark_file = app.path().app_config_dir().ok().map(|dir| dir.join("bookmarks.json"));
+            app.manage(BookmarkStore::open(bookmark_file));
+            let recent_file = app.path().app_data_dir().ok().map(|dir| dir.join("recent.json"));
+            app.manage(RecentFiles::open(recent_file));
+            let template_dir = app.path().app_data_dir().ok().map(|dir| dir.join("templates"));
+            app.manage(TemplateStore::open(template_dir));
+            
             if let Some(win) = app.get_webview_window("main") {
                 let _ = win.show();
                 let _ = win.set_focus();
@@ -499,19 +1435,120 @@ pub fn run() {
             greet,
             get_current_directory,
             list_directory,
+            list_directory_paged,
+            count_directory_entries,
+            stream::stream_directory,
             navigate_to_path,
+            get_file_metadata,
+            stat_many,
+            probe_path,
+            set_file_times,
+            permissions::get_permissions,
+            permissions::set_permissions,
+            permissions::set_executable,
+            permissions::can_write,
+            permissions::can_read,
+            identity::get_file_identity,
+            paths::canonicalize_path,
+            paths::normalize_path,
+            paths::get_path_components,
+            paths::relative_path,
+            paths::validate_name,
             get_home_directory,
             create_folder,
             create_file,
+            templates::list_templates,
+            templates::get_templates_directory,
+            templates::create_from_template,
+            create_symlink,
             delete_item,
+            trash_item,
+            trash_bin::trash_items,
+            trash_bin::get_trash_size,
+            trash_bin::empty_trash,
+            glob_delete::delete_by_glob,
+            empty_dirs::find_empty_directories,
+            empty_dirs::remove_empty_directories,
+            trash_bin::restore_from_trash,
+            undo::undo_last_operation,
+            undo::get_undo_history,
+            operation_log::enable_operation_log,
+            operation_log::disable_operation_log,
+            operation_log::get_operation_log,
             rename_item,
-            copy_items,
-            move_items,
+            transfer::rename_to,
+            batch_rename::batch_rename,
+            transfer::copy_items,
+            transfer::move_items,
+            transfer::duplicate_item,
             open_file_with_default_app,
+            launch::reveal_in_file_manager,
+            launch::open_with,
+            launch::get_default_app,
+            launch::open_terminal,
+            clipboard::copy_to_clipboard,
+            clipboard::copy_paths,
+            clipboard::set_clipboard_files,
+            clipboard::get_clipboard_files,
+            recent::get_recent_files,
+            recent::clear_recent_files,
             read_text_file,
+            read_file_range,
+            read_binary_file,
+            read_hex,
+            lines::read_head_lines,
+            lines::read_tail_lines,
+            lines::text_file_stats,
+            lines::offset_to_line_col,
+            tail::start_tail,
+            tail::stop_tail,
             write_text_file,
-            start_watch,
-            stop_watch
+            write_text_file_with_backup,
+            chunked_write::begin_write,
+            chunked_write::write_chunk,
+            chunked_write::finish_write,
+            chunked_write::abort_write,
+            hashing::compute_file_hash,
+            duplicates::find_duplicates,
+            mime::detect_mime_type,
+            search::search_files,
+            search::cancel_search,
+            name_index::build_index,
+            name_index::query_index,
+            name_index::drop_index,
+            tags::set_tags,
+            tags::get_tags,
+            tags::find_by_tag,
+            xattrs::list_xattrs,
+            xattrs::get_xattr,
+            xattrs::set_xattr,
+            xattrs::get_finder_tags,
+            xattrs::set_finder_tags,
+            bookmarks::add_bookmark,
+            bookmarks::remove_bookmark,
+            bookmarks::list_bookmarks,
+            thumbnail::generate_thumbnail,
+            icons::get_file_icon,
+            preview::get_preview,
+            dir_size::get_directory_size,
+            activity::recent_activity,
+            compare::compare_directories,
+            disks::get_disk_usage,
+            disks::list_volumes,
+            archive::create_archive,
+            archive::extract_archive,
+            archive::list_archive,
+            archive::read_archive_entry,
+            split::split_file,
+            split::join_files,
+            operations::cancel_operation,
+            watcher::start_watch,
+            watcher::watch_config,
+            watcher::watch_for_new,
+            watcher::watch_paths,
+            watcher::stop_watch,
+            watcher::stop_all_watches,
+            watcher::get_watch_status
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,309 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::{encoding, AppError};
+
+const MAX_LINE_COUNT: usize = 10_000;
+// Longer lines are cut here rather than loaded whole; `lines_cut` says so
+const MAX_LINE_BYTES: usize = 64 * 1024;
+// How far back read_tail_lines looks for line starts before giving up
+const MAX_TAIL_SCAN_BYTES: u64 = 16 * 1024 * 1024;
+const TAIL_CHUNK_BYTES: u64 = 64 * 1024;
+const STATS_CHUNK_BYTES: usize = 256 * 1024;
+
+// Lines are decoded as UTF-8 (invalid bytes replaced) with "\n" or "\r\n"
+// stripped. A missing final newline is fine.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileLines {
+    pub(crate) lines: Vec<String>,
+    has_more: bool,  // more lines after (head) or before (tail) these
+    lines_cut: bool, // some line was longer than MAX_LINE_BYTES and was shortened
+    size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TextFileStats {
+    lines: u64, // an unterminated last line counts, unlike `wc -l`
+    words: u64, // runs of non-whitespace
+    chars: u64,
+    bytes: u64,
+    encoding: String,
+}
+
+// 1-based; `column` counts characters, not bytes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LineColumn {
+    line: u64,
+    column: u64,
+}
+
+fn open_lines_file(path: &str, line_count: usize) -> Result<fs::File, AppError> {
+    if line_count > MAX_LINE_COUNT {
+        return Err(AppError::InvalidInput(format!("line_count is too large (max {})", MAX_LINE_COUNT)));
+    }
+    
+    if Path::new(path).is_dir() {
+        return Err(AppError::IsADirectory("Cannot read lines of a directory".to_string()));
+    }
+    
+    fs::File::open(path).map_err(|e| AppError::io("Failed to open file", e))
+}
+
+#[tauri::command]
+pub async fn read_head_lines(path: String, line_count: usize) -> Result<FileLines, AppError> {
+    let file = open_lines_file(&path, line_count)?;
+    let size = file.metadata().map_err(|e| AppError::io("Failed to read file metadata", e))?.len();
+    let mut reader = BufReader::new(file);
+    
+    let mut lines = Vec::new();
+    let mut lines_cut = false;
+    while lines.len() < line_count {
+        match read_capped_line(&mut reader).map_err(|e| AppError::io("Failed to read file", e))? {
+            Some((line, cut)) => {
+                lines_cut |= cut;
+                lines.push(line);
+            }
+            None => break,
+        }
+    }
+    
+    let has_more = !reader.fill_buf().map_err(|e| AppError::io("Failed to read file", e))?.is_empty();
+    
+    Ok(FileLines { lines, has_more, lines_cut, size })
+}
+
+// Reads one line, keeping at most MAX_LINE_BYTES of it and skipping the rest
+// without buffering it. None at end of file.
+fn read_capped_line(reader: &mut impl BufRead) -> std::io::Result<Option<(String, bool)>> {
+    let mut bytes = Vec::new();
+    let mut cut = false;
+    let mut saw_any = false;
+    
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+        saw_any = true;
+        
+        let (chunk, found_newline) = match buffer.iter().position(|&b| b == b'\n') {
+            Some(index) => (&buffer[..index], true),
+            None => (buffer, false),
+        };
+        
+        let room = MAX_LINE_BYTES.saturating_sub(bytes.len());
+        cut |= chunk.len() > room;
+        bytes.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        
+        let consumed = chunk.len() + usize::from(found_newline);
+        reader.consume(consumed);
+        if found_newline {
+            break;
+        }
+    }
+    
+    if !saw_any {
+        return Ok(None);
+    }
+    
+    Ok(Some((line_from_bytes(&bytes), cut)))
+}
+
+fn line_from_bytes(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+// Last `line_count` lines, found by scanning backwards from the end in
+// chunks, so only the tail of a large log is read
+#[tauri::command]
+pub async fn read_tail_lines(path: String, line_count: usize) -> Result<FileLines, AppError> {
+    let mut file = open_lines_file(&path, line_count)?;
+    let size = file.metadata().map_err(|e| AppError::io("Failed to read file metadata", e))?.len();
+    
+    // A final newline ends the last line rather than starting an empty one
+    let mut end = size;
+    if end > 0 {
+        let mut last = [0u8; 1];
+        file.seek(SeekFrom::Start(end - 1))
+            .and_then(|_| file.read_exact(&mut last))
+            .map_err(|e| AppError::io("Failed to read file", e))?;
+        if last[0] == b'\n' {
+            end -= 1;
+        }
+    }
+    
+    // Walk back until line_count newlines are behind us or the scan limit is hit
+    let scan_floor = end.saturating_sub(MAX_TAIL_SCAN_BYTES);
+    let mut start = end;
+    let mut newlines = 0;
+    let mut found_start = line_count == 0;
+    while !found_start && start > scan_floor {
+        let chunk_start = start.saturating_sub(TAIL_CHUNK_BYTES).max(scan_floor);
+        let mut chunk = vec![0u8; (start - chunk_start) as usize];
+        file.seek(SeekFrom::Start(chunk_start))
+            .and_then(|_| file.read_exact(&mut chunk))
+            .map_err(|e| AppError::io("Failed to read file", e))?;
+        
+        for (index, &byte) in chunk.iter().enumerate().rev() {
+            if byte == b'\n' {
+                newlines += 1;
+                if newlines == line_count {
+                    start = chunk_start + index as u64 + 1;
+                    found_start = true;
+                    break;
+                }
+            }
+        }
+        if !found_start {
+            start = chunk_start;
+        }
+    }
+    
+    let mut tail = vec![0u8; (end - start) as usize];
+    file.seek(SeekFrom::Start(start))
+        .and_then(|_| file.read_exact(&mut tail))
+        .map_err(|e| AppError::io("Failed to read file", e))?;
+    
+    let mut lines_cut = false;
+    let lines = if line_count == 0 || size == 0 {
+        Vec::new()
+    } else {
+        tail.split(|&b| b == b'\n')
+            .map(|line| {
+                lines_cut |= line.len() > MAX_LINE_BYTES;
+                // Keep the end of over-long lines, the part nearest the tail
+                line_from_bytes(&line[line.len().saturating_sub(MAX_LINE_BYTES)..])
+            })
+            .collect()
+    };
+    
+    Ok(FileLines { lines, has_more: start > 0, lines_cut, size })
+}
+
+// Counts in one streaming pass, decoding chunk by chunk with the encoding
+// detected from the start of the file, so memory stays flat at any size
+#[tauri::command]
+pub async fn text_file_stats(path: String) -> Result<TextFileStats, AppError> {
+    let file = open_lines_file(&path, 0)?;
+    
+    tauri::async_runtime::spawn_blocking(move || count_text(file))
+        .await
+        .map_err(|e| AppError::Internal(format!("Text stats task failed: {}", e)))?
+}
+
+fn count_text(mut file: fs::File) -> Result<TextFileStats, AppError> {
+    let mut buffer = vec![0u8; STATS_CHUNK_BYTES];
+    let mut filled = fill_chunk(&mut file, &mut buffer)?;
+    
+    if encoding::looks_binary(&buffer[..filled]) {
+        return Err(AppError::InvalidInput("File does not look like text".to_string()));
+    }
+    
+    let detected = encoding::detect_encoding(&buffer[..filled]);
+    let mut decoder = detected.new_decoder_with_bom_removal();
+    let mut stats = TextFileStats { encoding: detected.name().to_string(), ..Default::default() };
+    let mut text = String::new();
+    let mut in_word = false;
+    let mut last_char = None;
+    
+    loop {
+        let last = filled == 0;
+        stats.bytes += filled as u64;
+        
+        text.clear();
+        text.reserve(decoder.max_utf8_buffer_length(filled).unwrap_or(filled * 3 + 16));
+        let _ = decoder.decode_to_string(&buffer[..filled], &mut text, last);
+        
+        for c in text.chars() {
+            stats.chars += 1;
+            if c == '\n' {
+                stats.lines += 1;
+            }
+            if c.is_whitespace() {
+                in_word = false;
+            } else if !in_word {
+                in_word = true;
+                stats.words += 1;
+            }
+            last_char = Some(c);
+        }
+        
+        if last {
+            break;
+        }
+        filled = file.read(&mut buffer).map_err(|e| AppError::io("Failed to read file", e))?;
+    }
+    
+    if last_char.is_some_and(|c| c != '\n') {
+        stats.lines += 1;
+    }
+    
+    Ok(stats)
+}
+
+// Resolves a byte offset (such as a search match) to where it lands in the
+// decoded text. An offset inside a multibyte character points at that
+// character; a BOM takes up no column.
+#[tauri::command]
+pub async fn offset_to_line_col(path: String, offset: u64, encoding: Option<String>) -> Result<LineColumn, AppError> {
+    let file = open_lines_file(&path, 0)?;
+    let size = file.metadata().map_err(|e| AppError::io("Failed to read file metadata", e))?.len();
+    if offset > size {
+        return Err(AppError::InvalidInput(format!("Offset {} is past the end of the file ({} bytes)", offset, size)));
+    }
+    
+    tauri::async_runtime::spawn_blocking(move || locate_offset(file, offset, encoding.as_deref()))
+        .await
+        .map_err(|e| AppError::Internal(format!("Offset lookup task failed: {}", e)))?
+}
+
+fn locate_offset(file: fs::File, offset: u64, label: Option<&str>) -> Result<LineColumn, AppError> {
+    let mut file = file.take(offset);
+    let mut buffer = vec![0u8; STATS_CHUNK_BYTES];
+    let mut filled = fill_chunk(&mut file, &mut buffer)?;
+    
+    let detected = match label {
+        Some(label) => encoding::for_label(label)?,
+        None => encoding::detect_encoding(&buffer[..filled]),
+    };
+    let mut decoder = detected.new_decoder_with_bom_removal();
+    let mut position = LineColumn { line: 1, column: 1 };
+    let mut text = String::new();
+    
+    // Never told the input is finished, so a character cut by the offset
+    // stays pending instead of being counted
+    while filled > 0 {
+        text.clear();
+        text.reserve(decoder.max_utf8_buffer_length(filled).unwrap_or(filled * 3 + 16));
+        let _ = decoder.decode_to_string(&buffer[..filled], &mut text, false);
+        
+        for c in text.chars() {
+            if c == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else {
+                position.column += 1;
+            }
+        }
+        
+        filled = file.read(&mut buffer).map_err(|e| AppError::io("Failed to read file", e))?;
+    }
+    
+    Ok(position)
+}
+
+// Reads until `buffer` is full or the input ends, so encoding detection sees
+// a proper sample
+fn fill_chunk(file: &mut impl Read, buffer: &mut [u8]) -> Result<usize, AppError> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..]).map_err(|e| AppError::io("Failed to read file", e))? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
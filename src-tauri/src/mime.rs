@@ -0,0 +1,194 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+// Enough for every signature `infer` knows about, and for the text check
+const SNIFF_BYTES: u64 = 8 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MimeType {
+    pub(crate) mime: String,
+    pub(crate) category: String, // "image", "video", "audio", "text", "archive" or "binary"
+    source: String,              // "content" or "extension", whichever decided it
+}
+
+#[tauri::command]
+pub async fn detect_mime_type(path: String) -> Result<MimeType, AppError> {
+    let file_path = Path::new(&path);
+    
+    if file_path.is_dir() {
+        return Err(AppError::IsADirectory("Path is a directory".to_string()));
+    }
+    
+    sniff_file(file_path)
+}
+
+pub(crate) fn sniff_file(path: &Path) -> Result<MimeType, AppError> {
+    let mut head = Vec::with_capacity(SNIFF_BYTES as usize);
+    fs::File::open(path)
+        .and_then(|file| file.take(SNIFF_BYTES).read_to_end(&mut head))
+        .map_err(|e| AppError::io("Failed to read file", e))?;
+    
+    Ok(sniff(path, &head))
+}
+
+fn sniff(path: &Path, head: &[u8]) -> MimeType {
+    // Magic bytes win over whatever the extension claims
+    if let Some(kind) = infer::get(head) {
+        let category = match kind.matcher_type() {
+            infer::MatcherType::Image => "image",
+            infer::MatcherType::Video => "video",
+            infer::MatcherType::Audio => "audio",
+            infer::MatcherType::Text => "text",
+            infer::MatcherType::Archive => "archive",
+            _ => "binary",
+        };
+        return MimeType::new(kind.mime_type(), category, "content");
+    }
+    
+    let extension = path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if let Some((mime, category)) = mime_from_extension(&extension) {
+        return MimeType::new(mime, category, "extension");
+    }
+    
+    // Nothing recognised: decide between plain text and opaque bytes
+    if looks_like_text(head) {
+        MimeType::new("text/plain", "text", "content")
+    } else {
+        MimeType::new("application/octet-stream", "binary", "content")
+    }
+}
+
+impl MimeType {
+    fn new(mime: &str, category: &str, source: &str) -> Self {
+        MimeType {
+            mime: mime.to_string(),
+            category: category.to_string(),
+            source: source.to_string(),
+        }
+    }
+}
+
+// Formats without a reliable signature, mostly text-based ones
+fn mime_from_extension(extension: &str) -> Option<(&'static str, &'static str)> {
+    let mapping = match extension {
+        "txt" | "log" => ("text/plain", "text"),
+        "md" | "markdown" => ("text/markdown", "text"),
+        "csv" => ("text/csv", "text"),
+        "tsv" => ("text/tab-separated-values", "text"),
+        "json" => ("application/json", "text"),
+        "yaml" | "yml" => ("application/yaml", "text"),
+        "toml" => ("application/toml", "text"),
+        "js" | "mjs" | "cjs" => ("text/javascript", "text"),
+        "ts" | "tsx" => ("text/typescript", "text"),
+        "css" => ("text/css", "text"),
+        "html" | "htm" => ("text/html", "text"),
+        "xml" => ("application/xml", "text"),
+        "svg" => ("image/svg+xml", "image"),
+        "rs" | "py" | "c" | "h" | "cpp" | "go" | "java" | "sh" | "rb" => ("text/plain", "text"),
+        "tar" => ("application/x-tar", "archive"),
+        _ => return None,
+    };
+    Some(mapping)
+}
+
+// No NUL bytes and valid UTF-8, allowing a multi-byte character cut off by
+// the end of the sniffed window
+fn looks_like_text(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return false;
+    }
+    
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+// Language id for syntax highlighting, using the names most highlighters
+// share. Known file names (Makefile, Dockerfile, ...) and extensions come
+// first; extensionless scripts fall back to their shebang line.
+pub(crate) fn language_hint(path: &Path, content: &str) -> Option<&'static str> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    let by_name = match name.as_str() {
+        "makefile" | "gnumakefile" => Some("makefile"),
+        "dockerfile" | "containerfile" => Some("dockerfile"),
+        "cmakelists.txt" => Some("cmake"),
+        ".bashrc" | ".bash_profile" | ".zshrc" | ".profile" => Some("shell"),
+        "cargo.lock" => Some("toml"),
+        _ => None,
+    };
+    if by_name.is_some() {
+        return by_name;
+    }
+    
+    let extension = path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+    let by_extension = match extension.as_deref() {
+        Some("rs") => Some("rust"),
+        Some("py" | "pyw" | "pyi") => Some("python"),
+        Some("js" | "mjs" | "cjs") => Some("javascript"),
+        Some("jsx") => Some("jsx"),
+        Some("ts" | "mts" | "cts") => Some("typescript"),
+        Some("tsx") => Some("tsx"),
+        Some("md" | "markdown") => Some("markdown"),
+        Some("json" | "jsonc") => Some("json"),
+        Some("yaml" | "yml") => Some("yaml"),
+        Some("toml") => Some("toml"),
+        Some("html" | "htm") => Some("html"),
+        Some("css") => Some("css"),
+        Some("scss") => Some("scss"),
+        Some("xml" | "svg" | "plist") => Some("xml"),
+        Some("c" | "h") => Some("c"),
+        Some("cpp" | "cc" | "cxx" | "hpp" | "hh") => Some("cpp"),
+        Some("cs") => Some("csharp"),
+        Some("go") => Some("go"),
+        Some("java") => Some("java"),
+        Some("kt" | "kts") => Some("kotlin"),
+        Some("swift") => Some("swift"),
+        Some("rb") => Some("ruby"),
+        Some("php") => Some("php"),
+        Some("lua") => Some("lua"),
+        Some("sh" | "bash" | "zsh") => Some("shell"),
+        Some("ps1") => Some("powershell"),
+        Some("sql") => Some("sql"),
+        Some("vue") => Some("vue"),
+        Some("svelte") => Some("svelte"),
+        Some("ini" | "cfg") => Some("ini"),
+        _ => None,
+    };
+    if by_extension.is_some() || extension.is_some() {
+        return by_extension;
+    }
+    
+    shebang_language(content)
+}
+
+// "#!/usr/bin/env python3", "#!/bin/bash", "#!/usr/bin/env -S node --flag"
+fn shebang_language(content: &str) -> Option<&'static str> {
+    let line = content.strip_prefix("#!")?.lines().next()?;
+    let mut words = line.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    
+    // Versioned interpreters: python3.12, ruby2.7
+    let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    match program {
+        "python" => Some("python"),
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => Some("shell"),
+        "node" | "deno" | "bun" => Some("javascript"),
+        "ruby" => Some("ruby"),
+        "perl" => Some("perl"),
+        "php" => Some("php"),
+        "lua" => Some("lua"),
+        "fish" => Some("fish"),
+        "pwsh" => Some("powershell"),
+        _ => None,
+    }
+}
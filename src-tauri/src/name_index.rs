@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use notify::{RecursiveMode, Watcher};
+use notify_debouncer_full::{new_debouncer, Debouncer, FileIdMap};
+use serde::{Deserialize, Serialize};
+
+use crate::paths::display_path;
+use crate::AppError;
+
+// Short, so the index trails the disk by well under a second
+const INDEX_DEBOUNCE_MS: u64 = 200;
+const MAX_QUERY_LIMIT: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexStats {
+    root: String, // canonical; the key for `drop_index`
+    entries: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexMatch {
+    name: String,
+    path: String,
+    is_dir: bool,
+}
+
+// Every name under a root, kept twice: by lowercased name for prefix
+// queries, and by path so a removed folder's subtree can be dropped as one
+// contiguous range (a folder's descendants sort right after it).
+struct NameIndex {
+    root: PathBuf,
+    by_name: BTreeSet<(String, PathBuf)>,
+    by_path: BTreeMap<PathBuf, bool>, // path -> is_dir
+}
+
+// Indexes keyed by canonical root, each with the recursive watch that keeps
+// it current. Kept apart from `WatcherState`, whose watches are set up per
+// view and may not cover the whole tree.
+#[derive(Default)]
+pub struct NameIndexes(Mutex<HashMap<String, WatchedIndex>>);
+
+struct WatchedIndex {
+    index: Arc<Mutex<NameIndex>>,
+    // Never read, but dropping it is what stops the updates
+    _debouncer: Debouncer<notify::RecommendedWatcher, FileIdMap>,
+}
+
+impl NameIndex {
+    fn new(root: PathBuf) -> Self {
+        NameIndex { root, by_name: BTreeSet::new(), by_path: BTreeMap::new() }
+    }
+    
+    fn insert(&mut self, path: &Path, is_dir: bool) {
+        let Some(name) = path.file_name() else {
+            return;
+        };
+        self.by_name.insert((name.to_string_lossy().to_lowercase(), path.to_path_buf()));
+        self.by_path.insert(path.to_path_buf(), is_dir);
+    }
+    
+    // Adds `dir`'s contents and everything below; symlinks aren't followed,
+    // and unreadable folders are indexed without their contents
+    fn index_tree(&mut self, dir: &Path) {
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+                let path = entry.path();
+                self.insert(&path, is_dir);
+                if is_dir {
+                    pending.push(path);
+                }
+            }
+        }
+    }
+    
+    fn remove_tree(&mut self, path: &Path) {
+        let doomed: Vec<PathBuf> = self.by_path
+            .range(path.to_path_buf()..)
+            .map(|(indexed, _)| indexed)
+            .take_while(|indexed| indexed.starts_with(path))
+            .cloned()
+            .collect();
+        for indexed in doomed {
+            self.by_path.remove(&indexed);
+            if let Some(name) = indexed.file_name() {
+                self.by_name.remove(&(name.to_string_lossy().to_lowercase(), indexed.clone()));
+            }
+        }
+    }
+    
+    // Brings each changed path in line with the disk. Folders that appear
+    // (created, or moved in from outside) are walked, since their contents
+    // arrive without events of their own; a folder that's still there is
+    // left alone, as its children report their own changes.
+    fn apply(&mut self, paths: &[PathBuf], rescan: bool) {
+        if rescan {
+            self.by_name.clear();
+            self.by_path.clear();
+            let root = self.root.clone();
+            self.index_tree(&root);
+            return;
+        }
+        
+        let root = self.root.clone();
+        for path in paths.iter().filter(|path| path.starts_with(&root) && **path != root) {
+            match (self.by_path.get(path).copied(), fs::symlink_metadata(path)) {
+                (Some(was_dir), Ok(metadata)) if was_dir == metadata.is_dir() => {}
+                (_, Ok(metadata)) => {
+                    self.remove_tree(path);
+                    self.insert(path, metadata.is_dir());
+                    if metadata.is_dir() {
+                        self.index_tree(path);
+                    }
+                }
+                (_, Err(_)) => self.remove_tree(path),
+            }
+        }
+    }
+    
+    fn query(&self, prefix: &str, limit: usize, matches: &mut Vec<IndexMatch>) {
+        let start = (prefix.to_string(), PathBuf::new());
+        matches.extend(self.by_name.range(start..)
+            .take_while(|(name, _)| name.starts_with(prefix))
+            .take(limit)
+            .map(|(_, path)| IndexMatch {
+                name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+                path: display_path(path),
+                is_dir: self.by_path.get(path).copied().unwrap_or(false),
+            }));
+    }
+}
+
+// Indexes every name under `root` for `query_index`, then keeps the index
+// current with a recursive watch of its own. Building an index for a root
+// that has one replaces it.
+#[tauri::command]
+pub async fn build_index(root: String, indexes: tauri::State<'_, NameIndexes>) -> Result<IndexStats, AppError> {
+    if !Path::new(&root).is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", root)));
+    }
+    // Canonical, as some platforms report events under the resolved path
+    let root = fs::canonicalize(&root).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    
+    let index = Arc::new(Mutex::new(NameIndex::new(root.clone())));
+    let callback_index = index.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(INDEX_DEBOUNCE_MS),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            let (Ok(events), Ok(mut index)) = (result, callback_index.lock()) else {
+                return;
+            };
+            let rescan = events.iter().any(|event| event.need_rescan());
+            let paths: Vec<PathBuf> = events.into_iter().flat_map(|event| event.event.paths).collect();
+            index.apply(&paths, rescan);
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    debouncer.watcher().watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| AppError::Io(format!("Failed to watch directory: {}", e)))?;
+    
+    // Walked with the index locked and the watch already running, so changes
+    // made meanwhile wait and are applied on top of the finished walk
+    let walk_index = index.clone();
+    let entries = tauri::async_runtime::spawn_blocking(move || {
+        let mut index = walk_index.lock().map_err(|e| AppError::Internal(format!("Failed to lock index: {}", e)))?;
+        let root = index.root.clone();
+        index.index_tree(&root);
+        Ok::<_, AppError>(index.by_path.len())
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Index task failed: {}", e)))??;
+    
+    let key = display_path(&root);
+    indexes.0.lock()
+        .map_err(|e| AppError::Internal(format!("Failed to lock indexes: {}", e)))?
+        .insert(key.clone(), WatchedIndex { index, _debouncer: debouncer });
+    
+    Ok(IndexStats { root: key, entries })
+}
+
+// Names starting with `prefix` (case-insensitive), alphabetically, from the
+// index of `root` or from every index when no root is given
+#[tauri::command]
+pub async fn query_index(
+    prefix: String,
+    limit: usize,
+    root: Option<String>,
+    indexes: tauri::State<'_, NameIndexes>,
+) -> Result<Vec<IndexMatch>, AppError> {
+    if limit > MAX_QUERY_LIMIT {
+        return Err(AppError::InvalidInput(format!("limit is too large (max {})", MAX_QUERY_LIMIT)));
+    }
+    
+    let selected: Vec<Arc<Mutex<NameIndex>>> = {
+        let indexes = indexes.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock indexes: {}", e)))?;
+        match root {
+            Some(root) => {
+                let key = index_key(root);
+                let watched = indexes.get(&key)
+                    .ok_or_else(|| AppError::NotFound(format!("No index for {}; call build_index first", key)))?;
+                vec![watched.index.clone()]
+            }
+            None => indexes.values().map(|watched| watched.index.clone()).collect(),
+        }
+    };
+    
+    let prefix = prefix.to_lowercase();
+    let mut matches = Vec::new();
+    for index in selected {
+        if let Ok(index) = index.lock() {
+            index.query(&prefix, limit, &mut matches);
+        }
+    }
+    matches.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.path.cmp(&b.path)));
+    matches.truncate(limit);
+    
+    Ok(matches)
+}
+
+#[tauri::command]
+pub async fn drop_index(root: String, indexes: tauri::State<'_, NameIndexes>) -> Result<(), AppError> {
+    let key = index_key(root);
+    indexes.0.lock()
+        .map_err(|e| AppError::Internal(format!("Failed to lock indexes: {}", e)))?
+        .remove(&key);
+    Ok(())
+}
+
+// A root as given, resolved the way `build_index` keyed it. A root that's
+// gone since can still be dropped by the path `build_index` returned.
+fn index_key(root: String) -> String {
+    fs::canonicalize(&root).map(|path| display_path(&path)).unwrap_or(root)
+}
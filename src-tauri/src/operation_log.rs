@@ -0,0 +1,115 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+const DEFAULT_LOG_LIMIT: usize = 100;
+
+// One line of the log file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OperationRecord {
+    timestamp: DateTime<Utc>,
+    operation: String, // "create", "delete", "trash", "rename", "move" or "copy"
+    source: String,    // for "create", the new item
+    destination: Option<String>,
+    success: bool,
+    error: Option<String>,
+}
+
+// Where records go once `enable_operation_log` has been called. Every
+// command that creates, deletes, renames, moves or copies reports here;
+// until then recording does nothing.
+#[derive(Default)]
+pub struct OperationLog(Mutex<Option<LogFile>>);
+
+struct LogFile {
+    path: PathBuf,
+    file: fs::File,
+}
+
+impl OperationLog {
+    // A failure to write the log never fails the operation itself
+    pub(crate) fn record(&self, operation: &str, source: &Path, destination: Option<&Path>, error: Option<String>) {
+        let Ok(mut log) = self.0.lock() else {
+            return;
+        };
+        let Some(log) = log.as_mut() else {
+            return;
+        };
+        
+        let record = OperationRecord {
+            timestamp: Utc::now(),
+            operation: operation.to_string(),
+            source: source.to_string_lossy().to_string(),
+            destination: destination.map(|path| path.to_string_lossy().to_string()),
+            success: error.is_none(),
+            error,
+        };
+        let Ok(mut line) = serde_json::to_string(&record) else {
+            return;
+        };
+        line.push('\n');
+        
+        // One write per record, so appends from other machines on a shared
+        // drive don't interleave within a line
+        if let Err(e) = log.file.write_all(line.as_bytes()) {
+            eprintln!("Failed to write operation log {}: {}", log.path.display(), e);
+        }
+    }
+    
+    pub(crate) fn record_result<T>(&self, operation: &str, source: &Path, destination: Option<&Path>, result: &Result<T, AppError>) {
+        self.record(operation, source, destination, result.as_ref().err().map(|e| e.to_string()));
+    }
+}
+
+// Starts appending a JSON line per file operation to `path`, which is created
+// if needed; an existing log is added to. Logging lasts until the app quits,
+// so call this again at startup to keep it on.
+#[tauri::command]
+pub async fn enable_operation_log(path: String, log: tauri::State<'_, OperationLog>) -> Result<(), AppError> {
+    let log_path = PathBuf::from(&path);
+    if log_path.is_dir() {
+        return Err(AppError::IsADirectory("The log path is a directory".to_string()));
+    }
+    
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| AppError::io("Failed to open operation log", e))?;
+    
+    let mut current = log.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock operation log: {}", e)))?;
+    *current = Some(LogFile { path: log_path, file });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn disable_operation_log(log: tauri::State<'_, OperationLog>) -> Result<(), AppError> {
+    let mut current = log.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock operation log: {}", e)))?;
+    *current = None;
+    Ok(())
+}
+
+// The last `limit` records (100 by default), newest first. Lines that aren't
+// records, say from a log being written by a newer version, are skipped.
+#[tauri::command]
+pub async fn get_operation_log(
+    limit: Option<usize>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<Vec<OperationRecord>, AppError> {
+    let path = log.0.lock()
+        .map_err(|e| AppError::Internal(format!("Failed to lock operation log: {}", e)))?
+        .as_ref()
+        .map(|log| log.path.to_string_lossy().to_string())
+        .ok_or_else(|| AppError::InvalidInput("Operation logging is not enabled".to_string()))?;
+    
+    let lines = crate::lines::read_tail_lines(path, limit.unwrap_or(DEFAULT_LOG_LIMIT)).await?.lines;
+    Ok(lines.iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
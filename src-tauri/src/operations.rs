@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::AppError;
+
+// Long-running commands register here so `cancel_operation` can stop them.
+// Each operation polls its flag between units of work.
+#[derive(Default)]
+pub struct OperationRegistry {
+    operations: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    next_id: AtomicU64,
+}
+
+impl OperationRegistry {
+    // Registers an operation under the caller-supplied id, or a fresh
+    // "<prefix>-<n>" id when none is given. An id that's still running is
+    // refused, as replacing it would leave the first one uncancellable.
+    pub fn register(&self, id: Option<String>, prefix: &str) -> Result<(String, Arc<AtomicBool>), AppError> {
+        let id = id.unwrap_or_else(|| {
+            format!("{}-{}", prefix, self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        });
+        let flag = Arc::new(AtomicBool::new(false));
+        
+        let mut operations = self.operations.lock()
+            .map_err(|e| AppError::Internal(format!("Failed to lock operation registry: {}", e)))?;
+        if operations.contains_key(&id) {
+            return Err(AppError::AlreadyExists(format!("An operation with id '{}' is already running", id)));
+        }
+        operations.insert(id.clone(), flag.clone());
+        
+        Ok((id, flag))
+    }
+    
+    pub fn finish(&self, id: &str) {
+        if let Ok(mut operations) = self.operations.lock() {
+            operations.remove(id);
+        }
+    }
+    
+    pub fn cancel(&self, id: &str) -> Result<bool, AppError> {
+        let operations = self.operations.lock()
+            .map_err(|e| AppError::Internal(format!("Failed to lock operation registry: {}", e)))?;
+        
+        match operations.get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+// Returns false when no operation with that id is running
+#[tauri::command]
+pub async fn cancel_operation(
+    operation_id: String,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<bool, AppError> {
+    registry.cancel(&operation_id)
+}
@@ -0,0 +1,404 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+// Absolute path with every symlink, "." and ".." resolved. The path must exist.
+#[tauri::command]
+pub async fn canonicalize_path(path: String) -> Result<String, AppError> {
+    let canonical = fs::canonicalize(&path).map_err(|e| AppError::io("Failed to canonicalize path", e))?;
+    Ok(display_path(&canonical))
+}
+
+// Cleans up "." and ".." purely lexically, so it works for paths that don't
+// exist yet. Symlinks are not resolved, which means "link/.." may differ from
+// what the filesystem would give; relative paths stay relative.
+#[tauri::command]
+pub async fn normalize_path(path: String) -> Result<String, AppError> {
+    if path.is_empty() {
+        return Err(AppError::InvalidInput("Path is empty".to_string()));
+    }
+    
+    Ok(normalize(Path::new(&path)).to_string_lossy().to_string())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PathComponent {
+    name: String,
+    path: String,
+    exists: bool,
+}
+
+// Breadcrumbs from the root down to `path`. A drive or UNC share and the root
+// after it form one first component, so `C:\Users\me` starts at "C:" and
+// `/Users/me` at "/". Relative paths are resolved against the current directory.
+#[tauri::command]
+pub async fn get_path_components(path: String) -> Result<Vec<PathComponent>, AppError> {
+    if path.is_empty() {
+        return Err(AppError::InvalidInput("Path is empty".to_string()));
+    }
+    
+    let absolute = std::path::absolute(&path).map_err(|e| AppError::io("Failed to resolve path", e))?;
+    let mut current = PathBuf::new();
+    let mut components: Vec<PathComponent> = Vec::new();
+    
+    for component in normalize(&absolute).components() {
+        current.push(component);
+        let name = match component {
+            Component::Prefix(prefix) => prefix.as_os_str().to_string_lossy().to_string(),
+            Component::RootDir => match components.pop() {
+                Some(prefix) => prefix.name, // the drive and its root are one breadcrumb
+                None => std::path::MAIN_SEPARATOR_STR.to_string(),
+            },
+            other => other.as_os_str().to_string_lossy().to_string(),
+        };
+        components.push(PathComponent {
+            name,
+            path: current.to_string_lossy().to_string(),
+            exists: current.exists(),
+        });
+    }
+    
+    Ok(components)
+}
+
+// The path that leads from the folder `from` to `to`, like "../sibling/file.txt",
+// worked out lexically: neither needs to exist and symlinks aren't resolved.
+// Relative inputs are taken from the current directory. Paths on different
+// Windows drives or shares have no relative path, so `to` comes back absolute.
+#[tauri::command]
+pub async fn relative_path(from: String, to: String) -> Result<String, AppError> {
+    if from.is_empty() || to.is_empty() {
+        return Err(AppError::InvalidInput("Path is empty".to_string()));
+    }
+    
+    let resolve = |path: &str| std::path::absolute(path).map_err(|e| AppError::io("Failed to resolve path", e));
+    Ok(lexical_relative(&resolve(&from)?, &resolve(&to)?).to_string_lossy().to_string())
+}
+
+// `relative_path` on paths that are already absolute
+fn lexical_relative(from: &Path, to: &Path) -> PathBuf {
+    let (from, to) = (normalize(from), normalize(to));
+    let from_parts: Vec<Component> = from.components().collect();
+    let to_parts: Vec<Component> = to.components().collect();
+    
+    let same_root = match (from_parts.first(), to_parts.first()) {
+        (Some(Component::Prefix(a)), Some(Component::Prefix(b))) => same_component(a.as_os_str(), b.as_os_str()),
+        (Some(Component::Prefix(_)), _) | (_, Some(Component::Prefix(_))) => false,
+        _ => true,
+    };
+    if !same_root {
+        return to;
+    }
+    
+    let shared = from_parts.iter()
+        .zip(&to_parts)
+        .take_while(|(a, b)| same_component(a.as_os_str(), b.as_os_str()))
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in shared..from_parts.len() {
+        relative.push("..");
+    }
+    relative.extend(&to_parts[shared..]);
+    
+    if relative.as_os_str().is_empty() {
+        return PathBuf::from(".");
+    }
+    relative
+}
+
+// Windows paths compare case-insensitively, "C:" and "c:" included
+#[cfg(windows)]
+fn same_component(a: &std::ffi::OsStr, b: &std::ffi::OsStr) -> bool {
+    a.to_string_lossy().to_lowercase() == b.to_string_lossy().to_lowercase()
+}
+
+#[cfg(not(windows))]
+fn same_component(a: &std::ffi::OsStr, b: &std::ffi::OsStr) -> bool {
+    a == b
+}
+
+// Why `name` can't be used for a new item (in `parent`, when given, so the
+// full path's length is checked too), or None when it's fine. Only the rules
+// of the platform the app runs on apply: ":" is fine on Linux, for one.
+#[tauri::command]
+pub async fn validate_name(name: String, parent: Option<String>) -> Result<Option<String>, AppError> {
+    Ok(check_name(&name, parent.as_deref().map(Path::new)).err().map(|e| e.to_string()))
+}
+
+// Run by commands that create or rename, so a bad name gets a clear error
+// rather than whatever the OS makes of it
+pub(crate) fn check_name(name: &str, parent: Option<&Path>) -> Result<(), AppError> {
+    let reject = |reason: String| Err(AppError::InvalidInput(reason));
+    
+    if name.trim().is_empty() {
+        return reject("Name cannot be empty".to_string());
+    }
+    if name == "." || name == ".." {
+        return reject(format!("'{}' is reserved and cannot be used as a name", name));
+    }
+    if name.contains('/') || name.contains(std::path::MAIN_SEPARATOR) {
+        return reject("Name cannot contain a path separator".to_string());
+    }
+    if name.contains('\0') {
+        return reject("Name cannot contain a null character".to_string());
+    }
+    if let Some(reason) = platform_name_problem(name) {
+        return reject(reason);
+    }
+    
+    match parent.and_then(|parent| path_length_problem(&parent.join(name))) {
+        Some(reason) => reject(reason),
+        None => Ok(()),
+    }
+}
+
+#[cfg(windows)]
+fn platform_name_problem(name: &str) -> Option<String> {
+    const RESERVED: [&str; 4] = ["CON", "PRN", "AUX", "NUL"];
+    
+    if let Some(c) = name.chars().find(|c| matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') || (*c as u32) < 32) {
+        return Some(match c {
+            c if (c as u32) < 32 => "Name cannot contain control characters".to_string(),
+            c => format!("Name cannot contain '{}' on Windows; characters < > : \" / \\ | ? * are not allowed", c),
+        });
+    }
+    if name.ends_with([' ', '.']) {
+        return Some("Name cannot end with a space or a period on Windows".to_string());
+    }
+    
+    // Device names are reserved with any extension too, so "con.txt" is out
+    let stem = name.split('.').next().unwrap_or(name).trim_end().to_uppercase();
+    let numbered = ["COM", "LPT"].iter().any(|device| {
+        stem.strip_prefix(device)
+            .is_some_and(|n| matches!(n, "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "¹" | "²" | "³"))
+    });
+    if RESERVED.contains(&stem.as_str()) || numbered {
+        return Some(format!("'{}' is a reserved device name on Windows; choose another name", stem));
+    }
+    
+    if name.encode_utf16().count() > 255 {
+        return Some("Name is longer than the 255 characters Windows allows".to_string());
+    }
+    None
+}
+
+#[cfg(not(windows))]
+fn platform_name_problem(name: &str) -> Option<String> {
+    // NAME_MAX on nearly every filesystem in use
+    (name.len() > 255).then(|| format!("Name is {} bytes long; the limit is 255", name.len()))
+}
+
+// Without the long path opt-in, most Windows programs (Explorer included)
+// can't handle paths of MAX_PATH (260) characters or more. Verbatim
+// "\\?\" paths are exempt.
+#[cfg(windows)]
+fn path_length_problem(path: &Path) -> Option<String> {
+    use std::os::windows::ffi::OsStrExt;
+    
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    if absolute.as_os_str().to_string_lossy().starts_with(r"\\?\") {
+        return None;
+    }
+    let length = absolute.as_os_str().encode_wide().count();
+    (length >= 260).then(|| format!(
+        "The full path would be {} characters, over the 259 Windows allows; use a shorter name or a less deeply nested folder",
+        length
+    ))
+}
+
+#[cfg(not(windows))]
+fn path_length_problem(path: &Path) -> Option<String> {
+    // PATH_MAX on Linux; macOS allows 1024
+    const MAX_PATH_BYTES: usize = if cfg!(target_os = "macos") { 1024 } else { 4096 };
+    
+    let length = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()).as_os_str().len();
+    (length >= MAX_PATH_BYTES).then(|| format!(
+        "The full path would be {} bytes, over the {} allowed; use a shorter name or a less deeply nested folder",
+        length,
+        MAX_PATH_BYTES - 1
+    ))
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut parts: Vec<Component> = Vec::new();
+    
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match parts.last() {
+                Some(Component::Normal(_)) => {
+                    parts.pop();
+                }
+                // ".." at the root is the root
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => parts.push(component),
+            },
+            other => parts.push(other),
+        }
+    }
+    
+    if parts.is_empty() {
+        return PathBuf::from(".");
+    }
+    parts.iter().collect()
+}
+
+// The path to hand to `fs` functions. On Windows that's the verbatim
+// "\\?\C:\..." or "\\?\UNC\server\share\..." form, which lifts the 260
+// character MAX_PATH limit; paths that are already verbatim or device paths,
+// or that aren't valid Unicode, are left alone. Elsewhere it's the path as is.
+#[cfg(windows)]
+pub(crate) fn extended_path(path: &Path) -> PathBuf {
+    // Verbatim paths skip Windows' own cleanup, so resolve "." and ".." and
+    // turn "/" into "\" first; `absolute` does all of that
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    let Some(text) = absolute.to_str() else {
+        return absolute;
+    };
+    
+    if text.starts_with(r"\\?\") || text.starts_with(r"\\.\") {
+        return absolute;
+    }
+    match text.strip_prefix(r"\\") {
+        Some(share) => PathBuf::from(format!(r"\\?\UNC\{}", share)),
+        None => PathBuf::from(format!(r"\\?\{}", text)),
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn extended_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+// `fs::canonicalize` on Windows returns verbatim "\\?\C:\..." paths, which never
+// compare equal to the plain paths used everywhere else. Strip the prefix
+// when the path is representable without it.
+#[cfg(windows)]
+pub(crate) fn display_path(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    match text.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => rest.to_string(),
+        Some(rest) if rest.starts_with(r"UNC\") => format!(r"\\{}", &rest[4..]),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn display_path(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[cfg(windows)]
+    fn deep_drive_path() -> PathBuf {
+        let mut path = PathBuf::from(r"C:\");
+        for level in 0..30 {
+            path.push(format!("nested-folder-{:02}", level));
+        }
+        path.push("file.txt");
+        path
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn extended_path_prefixes_long_drive_paths() {
+        let path = deep_drive_path();
+        assert!(path.as_os_str().len() > 260);
+        
+        let extended = extended_path(&path);
+        assert_eq!(extended.as_os_str(), format!(r"\\?\{}", path.display()).as_str());
+        assert_eq!(display_path(&extended), path.to_string_lossy());
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn extended_path_maps_unc_shares() {
+        let path = PathBuf::from(r"\\server\share\reports\2024\summary.txt");
+        
+        let extended = extended_path(&path);
+        assert_eq!(extended, PathBuf::from(r"\\?\UNC\server\share\reports\2024\summary.txt"));
+        assert_eq!(display_path(&extended), path.to_string_lossy());
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn extended_path_leaves_verbatim_paths_alone() {
+        let drive = PathBuf::from(format!(r"\\?\{}", deep_drive_path().display()));
+        let share = PathBuf::from(r"\\?\UNC\server\share\reports\summary.txt");
+        
+        for verbatim in [drive, share] {
+            assert_eq!(extended_path(&verbatim), verbatim);
+            assert_eq!(extended_path(Path::new(&display_path(&verbatim))), verbatim);
+        }
+    }
+    
+    #[cfg(not(windows))]
+    #[test]
+    fn lexical_relative_between_siblings() {
+        let relative = lexical_relative(Path::new("/home/me/docs"), Path::new("/home/me/sibling/file.txt"));
+        assert_eq!(relative, PathBuf::from("../sibling/file.txt"));
+    }
+    
+    #[cfg(not(windows))]
+    #[test]
+    fn lexical_relative_up_and_down() {
+        assert_eq!(lexical_relative(Path::new("/home/me/docs/notes"), Path::new("/home")), PathBuf::from("../../.."));
+        assert_eq!(lexical_relative(Path::new("/home"), Path::new("/home/me/docs")), PathBuf::from("me/docs"));
+        assert_eq!(lexical_relative(Path::new("/"), Path::new("/etc/hosts")), PathBuf::from("etc/hosts"));
+    }
+    
+    #[cfg(not(windows))]
+    #[test]
+    fn lexical_relative_of_a_path_to_itself() {
+        assert_eq!(lexical_relative(Path::new("/home/me"), Path::new("/home/me")), PathBuf::from("."));
+        assert_eq!(lexical_relative(Path::new("/"), Path::new("/")), PathBuf::from("."));
+    }
+    
+    #[cfg(not(windows))]
+    #[test]
+    fn lexical_relative_ignores_trailing_separators_and_resolves_dot_dot() {
+        assert_eq!(lexical_relative(Path::new("/home/me/"), Path::new("/home/me/docs/")), PathBuf::from("docs"));
+        assert_eq!(lexical_relative(Path::new("/home/me/docs/.."), Path::new("/home/me")), PathBuf::from("."));
+        assert_eq!(
+            lexical_relative(Path::new("/home/me/./docs"), Path::new("/home/you/../me/music/./a.mp3")),
+            PathBuf::from("../music/a.mp3")
+        );
+        // ".." above the root stays at the root
+        assert_eq!(lexical_relative(Path::new("/../home"), Path::new("/home/me")), PathBuf::from("me"));
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn lexical_relative_on_windows() {
+        assert_eq!(
+            lexical_relative(Path::new(r"C:\Users\me\docs"), Path::new(r"c:\users\me\music\a.mp3")),
+            PathBuf::from(r"..\music\a.mp3")
+        );
+        assert_eq!(lexical_relative(Path::new(r"C:\Users\me"), Path::new(r"C:\Users\me\")), PathBuf::from("."));
+    }
+    
+    #[cfg(windows)]
+    #[test]
+    fn lexical_relative_across_drives_is_absolute() {
+        assert_eq!(lexical_relative(Path::new(r"C:\Users\me"), Path::new(r"D:\backup\a.txt")), PathBuf::from(r"D:\backup\a.txt"));
+        assert_eq!(
+            lexical_relative(Path::new(r"C:\Users\me"), Path::new(r"\\server\share\a.txt")),
+            PathBuf::from(r"\\server\share\a.txt")
+        );
+    }
+    
+    #[cfg(not(windows))]
+    #[test]
+    fn extended_path_is_the_identity_elsewhere() {
+        let path = PathBuf::from("/tmp").join("nested-folder/".repeat(30)).join("file.txt");
+        assert_eq!(extended_path(&path), path);
+        assert_eq!(display_path(&path), path.to_string_lossy());
+    }
+}
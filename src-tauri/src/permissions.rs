@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::{permission_mode, AppError};
+
+// Unix exposes mode bits; Windows only has the read-only attribute, so `mode`
+// and `symbolic` are None there rather than a made-up equivalent
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilePermissions {
+    path: String,
+    platform: String,         // "unix" or "windows"
+    mode: Option<u32>,        // e.g. 0o755, including setuid/setgid/sticky
+    symbolic: Option<String>, // e.g. "rwxr-xr-x"
+    readonly: bool,
+}
+
+// Symlinks are followed, as chmod does
+#[tauri::command]
+pub async fn get_permissions(path: String) -> Result<FilePermissions, AppError> {
+    let item_path = Path::new(&path);
+    let metadata = fs::metadata(item_path).map_err(|e| AppError::io("Failed to read permissions", e))?;
+    let mode = permission_mode(&metadata);
+    
+    Ok(FilePermissions {
+        path: item_path.to_string_lossy().to_string(),
+        platform: if cfg!(unix) { "unix" } else { "windows" }.to_string(),
+        mode,
+        symbolic: mode.map(symbolic_mode),
+        readonly: metadata.permissions().readonly(),
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutableState {
+    path: String,
+    supported: bool,          // false on Windows, where nothing was changed
+    executable: Option<bool>, // None when unsupported
+    mode: Option<u32>,
+}
+
+// "Make this script runnable": on Unix, turning it on adds execute for
+// everyone who can read the file, as `chmod +x` does with a typical umask;
+// turning it off clears every execute bit. Windows decides what runs by
+// extension, so there this changes nothing and reports `supported: false`.
+#[tauri::command]
+pub async fn set_executable(path: String, executable: bool) -> Result<ExecutableState, AppError> {
+    let item_path = Path::new(&path);
+    let metadata = fs::metadata(item_path).map_err(|e| AppError::io("Failed to read permissions", e))?;
+    if metadata.is_dir() {
+        return Err(AppError::IsADirectory("Folders can't be made executable".to_string()));
+    }
+    
+    let Some(mode) = permission_mode(&metadata) else {
+        return Ok(ExecutableState {
+            path: item_path.to_string_lossy().to_string(),
+            supported: false,
+            executable: None,
+            mode: None,
+        });
+    };
+    
+    let new_mode = if executable { mode | ((mode & 0o444) >> 2) | 0o100 } else { mode & !0o111 };
+    if new_mode != mode {
+        fs::set_permissions(item_path, with_mode(metadata.permissions(), new_mode)?)
+            .map_err(|e| AppError::io("Failed to set permissions", e))?;
+    }
+    
+    Ok(ExecutableState {
+        path: item_path.to_string_lossy().to_string(),
+        supported: true,
+        executable: Some(executable),
+        mode: Some(new_mode),
+    })
+}
+
+// Whether this process could write to `path`, for showing a lock before
+// any edits are made. Takes the user's own permissions, the read-only
+// attribute and read-only mounts into account. A path that doesn't exist yet
+// is writable if its folder is, so it can be created there.
+#[tauri::command]
+pub async fn can_write(path: String) -> Result<bool, AppError> {
+    let target = existing_target(Path::new(&path))?;
+    if crate::disks::is_read_only_volume(target) {
+        return Ok(false);
+    }
+    Ok(access_allowed(target, Access::Write))
+}
+
+// Whether this process could read `path`: open a file, or list a folder
+#[tauri::command]
+pub async fn can_read(path: String) -> Result<bool, AppError> {
+    let item_path = Path::new(&path);
+    if !item_path.exists() {
+        return Err(AppError::NotFound(format!("{} does not exist", path)));
+    }
+    Ok(access_allowed(item_path, Access::Read))
+}
+
+// `path`, or its folder when it doesn't exist yet
+fn existing_target(path: &Path) -> Result<&Path, AppError> {
+    if path.exists() {
+        return Ok(path);
+    }
+    match path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        Some(parent) if parent.is_dir() => Ok(parent),
+        _ => Err(AppError::NotFound(format!("{} does not exist", path.display()))),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Access {
+    Read,
+    Write,
+}
+
+// access(2) checks the real user, groups and ACLs, and reports EROFS too
+#[cfg(unix)]
+fn access_allowed(path: &Path, access: Access) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mode = match access {
+        Access::Read => libc::R_OK,
+        Access::Write => libc::W_OK,
+    };
+    unsafe { libc::access(c_path.as_ptr(), mode) == 0 }
+}
+
+// Windows has no access(2); opening is the only check that respects ACLs.
+// Opening for write without truncating leaves the file as it was. A
+// folder's read-only attribute doesn't stop files being added, so folders
+// are writable unless their volume isn't.
+#[cfg(not(unix))]
+fn access_allowed(path: &Path, access: Access) -> bool {
+    let extended = crate::paths::extended_path(path);
+    match (access, path.is_dir()) {
+        (Access::Read, true) => fs::read_dir(&extended).is_ok(),
+        (Access::Read, false) => fs::File::open(&extended).is_ok(),
+        (Access::Write, true) => true,
+        (Access::Write, false) => {
+            fs::metadata(&extended).is_ok_and(|metadata| !metadata.permissions().readonly())
+                && fs::OpenOptions::new().write(true).open(&extended).is_ok()
+        }
+    }
+}
+
+// Give exactly one of `mode` (Unix only) or `readonly`. On Unix, `readonly`
+// clears every write bit, and clearing it gives back write to the owner only.
+#[tauri::command]
+pub async fn set_permissions(
+    path: String,
+    mode: Option<u32>,
+    readonly: Option<bool>,
+) -> Result<FilePermissions, AppError> {
+    let item_path = Path::new(&path);
+    let metadata = fs::metadata(item_path).map_err(|e| AppError::io("Failed to read permissions", e))?;
+    
+    let permissions = match (mode, readonly) {
+        (Some(mode), None) => {
+            if mode > 0o7777 {
+                return Err(AppError::InvalidInput(format!("Invalid mode {:o}, expected at most 7777", mode)));
+            }
+            with_mode(metadata.permissions(), mode)?
+        }
+        (None, Some(readonly)) => with_readonly(metadata.permissions(), readonly),
+        _ => return Err(AppError::InvalidInput("Give either a mode or readonly".to_string())),
+    };
+    
+    fs::set_permissions(item_path, permissions).map_err(|e| AppError::io("Failed to set permissions", e))?;
+    get_permissions(path).await
+}
+
+fn symbolic_mode(mode: u32) -> String {
+    // Each triplet's execute slot also shows setuid, setgid or sticky
+    let special = [(0o4000, 's'), (0o2000, 's'), (0o1000, 't')];
+    
+    (0..3).flat_map(|triplet| {
+        let bits = mode >> (6 - triplet * 3);
+        let (flag, mark) = special[triplet];
+        let execute = match (bits & 1 != 0, mode & flag != 0) {
+            (true, true) => mark,
+            (false, true) => mark.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        };
+        [
+            if bits & 4 != 0 { 'r' } else { '-' },
+            if bits & 2 != 0 { 'w' } else { '-' },
+            execute,
+        ]
+    })
+    .collect()
+}
+
+#[cfg(unix)]
+fn with_mode(mut permissions: fs::Permissions, mode: u32) -> Result<fs::Permissions, AppError> {
+    use std::os::unix::fs::PermissionsExt;
+    permissions.set_mode(mode);
+    Ok(permissions)
+}
+
+#[cfg(not(unix))]
+fn with_mode(_permissions: fs::Permissions, _mode: u32) -> Result<fs::Permissions, AppError> {
+    Err(AppError::Unsupported("Permission modes are Unix only; set readonly instead".to_string()))
+}
+
+#[cfg(unix)]
+fn with_readonly(mut permissions: fs::Permissions, readonly: bool) -> fs::Permissions {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = permissions.mode();
+    permissions.set_mode(if readonly { mode & !0o222 } else { mode | 0o200 });
+    permissions
+}
+
+#[cfg(not(unix))]
+fn with_readonly(mut permissions: fs::Permissions, readonly: bool) -> fs::Permissions {
+    // Only the read-only attribute exists here, so this can't open it to everyone
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(readonly);
+    permissions
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::decode_text;
+use crate::mime::sniff_file;
+use crate::thumbnail::{thumbnail_for, Thumbnail};
+use crate::AppError;
+
+const PREVIEW_LINES: usize = 20;
+const PREVIEW_TEXT_BYTES: u64 = 64 * 1024;
+const PREVIEW_THUMBNAIL_SIZE: u32 = 256;
+// PDFs normally keep the page tree near the start or in the trailer; larger
+// files past this are reported without a page count
+const PREVIEW_PDF_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Preview {
+    Text {
+        lines: Vec<String>,
+        truncated: bool, // more lines follow
+        encoding: String,
+    },
+    Image {
+        thumbnail: Thumbnail,
+    },
+    Media {
+        mime: String,
+        duration_seconds: Option<f64>,
+        codec: Option<String>,
+    },
+    Pdf {
+        page_count: Option<usize>,
+    },
+    Unsupported {
+        mime: String,
+    },
+}
+
+// Hover-card preview, dispatched on the sniffed MIME type. Reads are capped
+// per kind and media is only probed for its headers. Files that can't be
+// previewed come back as `Unsupported` instead of an error.
+#[tauri::command]
+pub async fn get_preview(path: String) -> Result<Preview, AppError> {
+    tauri::async_runtime::spawn_blocking(move || preview_for(Path::new(&path)))
+        .await
+        .map_err(|e| AppError::Internal(format!("Preview task failed: {}", e)))?
+}
+
+fn preview_for(path: &Path) -> Result<Preview, AppError> {
+    if !path.exists() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    
+    if path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot preview a directory".to_string()));
+    }
+    
+    let mime = sniff_file(path)?;
+    let unsupported = || Preview::Unsupported { mime: mime.mime.clone() };
+    
+    let preview = match mime.category.as_str() {
+        _ if mime.mime == "application/pdf" => Preview::Pdf { page_count: pdf_page_count(path) },
+        "text" => text_preview(path)?,
+        "image" => match thumbnail_for(path, PREVIEW_THUMBNAIL_SIZE) {
+            Ok(thumbnail) => Preview::Image { thumbnail },
+            Err(_) => unsupported(), // e.g. SVG or formats the decoder lacks
+        },
+        "video" | "audio" => {
+            let (duration_seconds, codec) = probe_media(path, &mime.category);
+            Preview::Media { mime: mime.mime.clone(), duration_seconds, codec }
+        }
+        _ => unsupported(),
+    };
+    
+    Ok(preview)
+}
+
+fn text_preview(path: &Path) -> Result<Preview, AppError> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(PREVIEW_TEXT_BYTES).read_to_end(&mut bytes))
+        .map_err(|e| AppError::io("Failed to read file", e))?;
+    
+    let decoded = decode_text(&bytes, None)?;
+    let mut lines = decoded.content.lines();
+    let first: Vec<String> = lines.by_ref().take(PREVIEW_LINES).map(str::to_string).collect();
+    let truncated = lines.next().is_some() || bytes.len() as u64 == PREVIEW_TEXT_BYTES;
+    
+    Ok(Preview::Text {
+        lines: first,
+        truncated,
+        encoding: decoded.encoding.name().to_string(),
+    })
+}
+
+// Duration and codec from `ffprobe` when it's installed; it only parses the
+// container headers. Both are None if it's missing or can't read the file.
+fn probe_media(path: &Path, category: &str) -> (Option<f64>, Option<String>) {
+    let output = Command::new("ffprobe")
+        .args(["-v", "quiet", "-print_format", "json", "-show_format", "-show_streams"])
+        .arg(path)
+        .output();
+    let probe: serde_json::Value = match output {
+        Ok(output) if output.status.success() => match serde_json::from_slice(&output.stdout) {
+            Ok(value) => value,
+            Err(_) => return (None, None),
+        },
+        _ => return (None, None),
+    };
+    
+    let duration = probe["format"]["duration"].as_str().and_then(|d| d.parse().ok());
+    let codec = probe["streams"].as_array()
+        .and_then(|streams| {
+            streams.iter()
+                .find(|stream| stream["codec_type"].as_str() == Some(category))
+                .or(streams.first())
+        })
+        .and_then(|stream| stream["codec_name"].as_str())
+        .map(str::to_string);
+    
+    (duration, codec)
+}
+
+// The page tree root's "/Count" is the total page count; the largest one
+// found wins since nested page-tree nodes carry partial counts. PDFs with
+// compressed object streams hide it and get None.
+fn pdf_page_count(path: &Path) -> Option<usize> {
+    let mut bytes = Vec::new();
+    fs::File::open(path).ok()?.take(PREVIEW_PDF_BYTES).read_to_end(&mut bytes).ok()?;
+    
+    let pages = regex::bytes::Regex::new(r"/Type\s*/Pages\b").ok()?;
+    let count = regex::bytes::Regex::new(r"/Count\s+(\d+)").ok()?;
+    
+    // Only look at "/Count" inside dictionaries that are "/Type /Pages"
+    bytes.split(|&b| b == b'>')
+        .filter(|dict| pages.is_match(dict))
+        .filter_map(|dict| count.captures(dict))
+        .filter_map(|captures| std::str::from_utf8(&captures[1]).ok()?.parse().ok())
+        .max()
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{write_atomic, AppError};
+
+// Oldest entries drop off once the list is this long
+const MAX_RECENT_FILES: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentFile {
+    path: String,
+    opened: DateTime<Utc>,
+}
+
+// Files opened through the app, newest first, persisted as JSON in the app
+// data folder. Opening a file again moves it back to the front.
+pub struct RecentFiles {
+    file: Option<PathBuf>, // None keeps the list in memory only
+    entries: Mutex<VecDeque<RecentFile>>,
+}
+
+impl RecentFiles {
+    pub fn open(file: Option<PathBuf>) -> Self {
+        let entries = file.as_ref()
+            .and_then(|file| fs::read(file).ok())
+            .and_then(|bytes| match serde_json::from_slice(&bytes) {
+                Ok(entries) => Some(entries),
+                Err(e) => {
+                    eprintln!("Ignoring unreadable recent files list: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        
+        RecentFiles { file, entries: Mutex::new(entries) }
+    }
+    
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, VecDeque<RecentFile>>, AppError> {
+        self.entries.lock().map_err(|e| AppError::Internal(format!("Failed to lock recent files: {}", e)))
+    }
+    
+    fn save(&self, entries: &VecDeque<RecentFile>) -> Result<(), AppError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io("Failed to create recent files folder", e))?;
+        }
+        let json = serde_json::to_vec_pretty(entries)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize recent files: {}", e)))?;
+        write_atomic(file, &json).map_err(|e| AppError::io("Failed to save recent files", e))
+    }
+    
+    // Best-effort, like tag bookkeeping: failing to remember a file must not
+    // fail opening it
+    pub(crate) fn record(&self, path: &Path) {
+        let Ok(canonical) = fs::canonicalize(path) else {
+            return;
+        };
+        let path = canonical.to_string_lossy().to_string();
+        let Ok(mut entries) = self.lock() else {
+            return;
+        };
+        
+        entries.retain(|entry| entry.path != path);
+        entries.push_front(RecentFile { path, opened: Utc::now() });
+        entries.truncate(MAX_RECENT_FILES);
+        
+        if let Err(e) = self.save(&entries) {
+            eprintln!("Failed to update recent files: {}", e);
+        }
+    }
+}
+
+// Newest first. Files that have since been moved or deleted are skipped but
+// kept, in case they come back.
+#[tauri::command]
+pub async fn get_recent_files(
+    limit: Option<usize>,
+    recent: tauri::State<'_, RecentFiles>,
+) -> Result<Vec<RecentFile>, AppError> {
+    let entries = recent.lock()?;
+    
+    Ok(entries.iter()
+        .filter(|entry| Path::new(&entry.path).is_file())
+        .take(limit.unwrap_or(MAX_RECENT_FILES))
+        .cloned()
+        .collect())
+}
+
+#[tauri::command]
+pub async fn clear_recent_files(recent: tauri::State<'_, RecentFiles>) -> Result<(), AppError> {
+    let mut entries = recent.lock()?;
+    entries.clear();
+    recent.save(&entries)
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::operations::OperationRegistry;
+use crate::{file_item, AppError, FileItem};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchResultEvent {
+    search_id: String,
+    item: FileItem,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchSummary {
+    search_id: String,
+    items: Vec<FileItem>,
+    cancelled: bool,
+    truncated: bool,
+}
+
+enum NameMatcher {
+    Substring { query: String, case_sensitive: bool },
+    Glob { pattern: glob::Pattern, options: glob::MatchOptions },
+    Regex(regex::Regex),
+}
+
+impl NameMatcher {
+    fn new(query: &str, mode: &str, case_sensitive: bool) -> Result<Self, AppError> {
+        match mode {
+            "substring" => Ok(NameMatcher::Substring {
+                query: if case_sensitive { query.to_string() } else { query.to_lowercase() },
+                case_sensitive,
+            }),
+            "glob" => {
+                let pattern = glob::Pattern::new(query)
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid glob pattern: {}", e)))?;
+                let options = glob::MatchOptions {
+                    case_sensitive,
+                    ..Default::default()
+                };
+                Ok(NameMatcher::Glob { pattern, options })
+            }
+            "regex" => {
+                let regex = regex::RegexBuilder::new(query)
+                    .case_insensitive(!case_sensitive)
+                    .build()
+                    .map_err(|e| AppError::InvalidInput(format!("Invalid regular expression: {}", e)))?;
+                Ok(NameMatcher::Regex(regex))
+            }
+            other => Err(AppError::InvalidInput(format!(
+                "Unknown search mode '{}', expected \"substring\", \"glob\" or \"regex\"",
+                other
+            ))),
+        }
+    }
+    
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Substring { query, case_sensitive: true } => name.contains(query.as_str()),
+            NameMatcher::Substring { query, case_sensitive: false } => {
+                name.to_lowercase().contains(query.as_str())
+            }
+            NameMatcher::Glob { pattern, options } => pattern.matches_with(name, *options),
+            NameMatcher::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+// Walks `root` matching entry names against `query`. Each match is emitted as a
+// `search-result` event as soon as it's found; the full list is also returned
+// once the walk ends. Pass a `search_id` to be able to `cancel_search` it.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn search_files(
+    root: String,
+    query: String,
+    mode: String,
+    max_results: Option<usize>,
+    case_sensitive: Option<bool>,
+    search_id: Option<String>,
+    app_handle: AppHandle,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<SearchSummary, AppError> {
+    let root_path = PathBuf::from(&root);
+    
+    if !root_path.is_dir() {
+        return Err(AppError::NotADirectory("Search root is not a directory".to_string()));
+    }
+    
+    let matcher = NameMatcher::new(&query, &mode, case_sensitive.unwrap_or(false))?;
+    
+    let (search_id, cancelled) = registry.register(search_id, "search")?;
+    
+    let walk_id = search_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        walk_and_match(&root_path, &matcher, max_results, &walk_id, &cancelled, &app_handle)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Search task failed: {}", e)));
+    
+    registry.finish(&search_id);
+    
+    let (items, cancelled, truncated) = result?;
+    
+    Ok(SearchSummary {
+        search_id,
+        items,
+        cancelled,
+        truncated,
+    })
+}
+
+#[tauri::command]
+pub async fn cancel_search(
+    search_id: String,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<bool, AppError> {
+    registry.cancel(&search_id)
+}
+
+fn walk_and_match(
+    root: &Path,
+    matcher: &NameMatcher,
+    max_results: Option<usize>,
+    search_id: &str,
+    cancelled: &AtomicBool,
+    app_handle: &AppHandle,
+) -> (Vec<FileItem>, bool, bool) {
+    let mut items = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue, // Unreadable directories are skipped, not fatal
+        };
+        
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            if cancelled.load(Ordering::Relaxed) {
+                return (items, true, false);
+            }
+            
+            let entry_path = entry.path();
+            let metadata = match fs::symlink_metadata(&entry_path) {
+                Ok(meta) => meta,
+                Err(_) => continue,
+            };
+            
+            // Symlinked directories are reported but never descended into
+            if metadata.is_dir() {
+                pending.push(entry_path.clone());
+            }
+            
+            if !matcher.matches(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+            
+            // Checked before anything is emitted, so `max_results: 0` finds nothing
+            if max_results.is_some_and(|max| items.len() >= max) {
+                return (items, false, true);
+            }
+            
+            let item = file_item(&entry_path, &metadata, items.len().to_string());
+            let _ = app_handle.emit("search-result", SearchResultEvent {
+                search_id: search_id.to_string(),
+                item: item.clone(),
+            });
+            items.push(item);
+        }
+    }
+    
+    (items, false, false)
+}
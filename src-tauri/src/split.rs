@@ -0,0 +1,328 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+
+use crate::{temp_sibling_path, write_atomic, AppError};
+
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+const PROGRESS_BYTES: u64 = 4 * 1024 * 1024;
+const MAX_PARTS: u64 = 10_000;
+const MANIFEST_VERSION: u32 = 1;
+
+// Written next to the parts as "<file name>.manifest.json"
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitManifest {
+    version: u32,
+    file_name: String,
+    size: u64,
+    chunk_size: u64,
+    algorithm: String, // always "sha256" for now
+    digest: String,    // of the whole file
+    parts: Vec<SplitPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitPart {
+    name: String, // a file name in the manifest's folder
+    size: u64,
+    digest: String,
+}
+
+// Emitted as "split-progress" and "join-progress"
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SplitProgress {
+    path: String, // the file being split, or the file being joined into
+    bytes_done: u64,
+    total_bytes: u64,
+    parts_done: usize,
+    total_parts: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SplitSummary {
+    manifest_path: String,
+    part_paths: Vec<String>,
+    size: u64,
+    digest: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinSummary {
+    output_path: String,
+    size: u64,
+    digest: String,
+}
+
+// Cuts `path` into `chunk_size`-byte parts in `output_dir`, named
+// "<file name>.part001" and on, plus a manifest listing them with their
+// hashes. The manifest is written last, so one that exists is complete.
+// Nothing is overwritten; if anything fails the parts written so far are
+// removed.
+#[tauri::command]
+pub async fn split_file(
+    path: String,
+    chunk_size: u64,
+    output_dir: String,
+    app_handle: AppHandle,
+) -> Result<SplitSummary, AppError> {
+    let source = PathBuf::from(&path);
+    let out_dir = PathBuf::from(&output_dir);
+    
+    let metadata = fs::metadata(&source).map_err(|e| AppError::io("Failed to read file", e))?;
+    if metadata.is_dir() {
+        return Err(AppError::IsADirectory("Cannot split a directory".to_string()));
+    }
+    if !out_dir.is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", output_dir)));
+    }
+    if chunk_size == 0 {
+        return Err(AppError::InvalidInput("chunk_size must be at least 1".to_string()));
+    }
+    
+    let total_parts = metadata.len().div_ceil(chunk_size).max(1);
+    if total_parts > MAX_PARTS {
+        return Err(AppError::InvalidInput(format!(
+            "That would make {} parts (max {}); use a larger chunk_size", total_parts, MAX_PARTS
+        )));
+    }
+    
+    let file_name = source.file_name()
+        .ok_or_else(|| AppError::InvalidInput(format!("Cannot split '{}'", path)))?
+        .to_string_lossy()
+        .to_string();
+    let width = total_parts.to_string().len().max(3);
+    let part_names: Vec<String> = (1..=total_parts)
+        .map(|number| format!("{}.part{:0width$}", file_name, number, width = width))
+        .collect();
+    let manifest_path = out_dir.join(format!("{}.manifest.json", file_name));
+    
+    if let Some(existing) = part_names.iter()
+        .map(|name| out_dir.join(name))
+        .chain([manifest_path.clone()])
+        .find(|target| target.exists())
+    {
+        return Err(AppError::AlreadyExists(format!("{} already exists", existing.display())));
+    }
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut written = Vec::new();
+        let result = write_parts(&source, chunk_size, &out_dir, &part_names, &mut written, |bytes_done, parts_done| {
+            let _ = app_handle.emit("split-progress", SplitProgress {
+                path: path.clone(),
+                bytes_done,
+                total_bytes: metadata.len(),
+                parts_done,
+                total_parts: part_names.len(),
+            });
+        })
+        .and_then(|manifest: SplitManifest| {
+            let json = serde_json::to_vec_pretty(&manifest)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+            write_atomic(&manifest_path, &json).map_err(|e| AppError::io("Failed to write manifest", e))?;
+            Ok(manifest)
+        });
+        
+        let manifest = match result {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                for part in &written {
+                    let _ = fs::remove_file(part);
+                }
+                return Err(e);
+            }
+        };
+        
+        Ok(SplitSummary {
+            manifest_path: manifest_path.to_string_lossy().to_string(),
+            part_paths: written.iter().map(|part| part.to_string_lossy().to_string()).collect(),
+            size: manifest.size,
+            digest: manifest.digest,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Split task failed: {}", e)))?
+}
+
+fn write_parts(
+    source: &Path,
+    chunk_size: u64,
+    out_dir: &Path,
+    part_names: &[String],
+    written: &mut Vec<PathBuf>,
+    mut progress: impl FnMut(u64, usize),
+) -> Result<SplitManifest, AppError> {
+    let mut input = fs::File::open(source).map_err(|e| AppError::io("Failed to open file", e))?;
+    let mut whole = Sha256::new();
+    let mut parts = Vec::new();
+    let mut bytes_done = 0u64;
+    
+    for name in part_names {
+        let part_path = out_dir.join(name);
+        let mut output = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&part_path)
+            .map_err(|e| AppError::io("Failed to create part", e))?;
+        written.push(part_path);
+        
+        let mut part = Sha256::new();
+        let size = copy_hashing(&mut (&mut input).take(chunk_size), &mut output, &mut [&mut part, &mut whole], |copied| {
+            progress(bytes_done + copied, parts.len());
+        })
+        .and_then(|size| output.sync_all().map(|_| size))
+        .map_err(|e| AppError::io("Failed to write part", e))?;
+        bytes_done += size;
+        
+        parts.push(SplitPart { name: name.clone(), size, digest: format!("{:x}", part.finalize()) });
+        progress(bytes_done, parts.len());
+    }
+    
+    // The file grew while it was being split
+    if input.read(&mut [0u8; 1]).map_err(|e| AppError::io("Failed to read file", e))? > 0 {
+        return Err(AppError::Io("The file changed while it was being split".to_string()));
+    }
+    
+    Ok(SplitManifest {
+        version: MANIFEST_VERSION,
+        file_name: source.file_name().unwrap_or_default().to_string_lossy().to_string(),
+        size: bytes_done,
+        chunk_size,
+        algorithm: "sha256".to_string(),
+        digest: format!("{:x}", whole.finalize()),
+        parts,
+    })
+}
+
+// Puts back together the file `split_file` described in `manifest_path`,
+// with the parts read from the manifest's folder. Each part's size and hash
+// are checked as it's read, then the whole file's hash; the output only
+// appears once everything matches.
+#[tauri::command]
+pub async fn join_files(
+    manifest_path: String,
+    output_path: String,
+    overwrite: Option<bool>,
+    app_handle: AppHandle,
+) -> Result<JoinSummary, AppError> {
+    let manifest_file = PathBuf::from(&manifest_path);
+    let output = PathBuf::from(&output_path);
+    
+    let bytes = fs::read(&manifest_file).map_err(|e| AppError::io("Failed to read manifest", e))?;
+    let manifest: SplitManifest = serde_json::from_slice(&bytes)
+        .map_err(|e| AppError::InvalidInput(format!("Not a split manifest: {}", e)))?;
+    if manifest.version > MANIFEST_VERSION {
+        return Err(AppError::Unsupported(format!("Manifest version {} is newer than this app", manifest.version)));
+    }
+    if manifest.algorithm != "sha256" {
+        return Err(AppError::Unsupported(format!("Unsupported hash algorithm '{}'", manifest.algorithm)));
+    }
+    
+    if output.is_dir() {
+        return Err(AppError::IsADirectory("Output path is a directory".to_string()));
+    }
+    if output.exists() && !overwrite.unwrap_or(false) {
+        return Err(AppError::AlreadyExists("Output file already exists".to_string()));
+    }
+    
+    // Part names are bare file names, so a manifest can't point elsewhere
+    let parts_dir = manifest_file.parent().unwrap_or(Path::new("."));
+    let mut part_paths = Vec::new();
+    for part in &manifest.parts {
+        if Path::new(&part.name).file_name() != Some(part.name.as_ref()) {
+            return Err(AppError::InvalidInput(format!("Invalid part name '{}'", part.name)));
+        }
+        let part_path = parts_dir.join(&part.name);
+        if !part_path.is_file() {
+            return Err(AppError::NotFound(format!("Missing part {}", part.name)));
+        }
+        part_paths.push(part_path);
+    }
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let temp_path = temp_sibling_path(&output);
+        let result = join_parts(&manifest, &part_paths, &temp_path, |bytes_done, parts_done| {
+            let _ = app_handle.emit("join-progress", SplitProgress {
+                path: output_path.clone(),
+                bytes_done,
+                total_bytes: manifest.size,
+                parts_done,
+                total_parts: manifest.parts.len(),
+            });
+        })
+        .and_then(|_| fs::rename(&temp_path, &output).map_err(|e| AppError::io("Failed to create output file", e)));
+        
+        if let Err(e) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(e);
+        }
+        
+        Ok(JoinSummary { output_path, size: manifest.size, digest: manifest.digest.clone() })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Join task failed: {}", e)))?
+}
+
+fn join_parts(
+    manifest: &SplitManifest,
+    part_paths: &[PathBuf],
+    temp_path: &Path,
+    mut progress: impl FnMut(u64, usize),
+) -> Result<(), AppError> {
+    let mut output = fs::File::create(temp_path).map_err(|e| AppError::io("Failed to create output file", e))?;
+    let mut whole = Sha256::new();
+    let mut bytes_done = 0u64;
+    
+    for (index, (part, part_path)) in manifest.parts.iter().zip(part_paths).enumerate() {
+        let mut input = fs::File::open(part_path).map_err(|e| AppError::io("Failed to open part", e))?;
+        let mut part_hasher = Sha256::new();
+        let size = copy_hashing(&mut input, &mut output, &mut [&mut part_hasher, &mut whole], |copied| {
+            progress(bytes_done + copied, index);
+        })
+        .map_err(|e| AppError::io("Failed to join part", e))?;
+        
+        if size != part.size || format!("{:x}", part_hasher.finalize()) != part.digest {
+            return Err(AppError::InvalidInput(format!("Part {} is damaged or incomplete", part.name)));
+        }
+        bytes_done += size;
+        progress(bytes_done, index + 1);
+    }
+    
+    if bytes_done != manifest.size || format!("{:x}", whole.finalize()) != manifest.digest {
+        return Err(AppError::InvalidInput("The joined file doesn't match the manifest's hash".to_string()));
+    }
+    output.sync_all().map_err(|e| AppError::io("Failed to write output file", e))
+}
+
+// Copies until `reader` runs out, feeding every hasher along the way, and
+// reports the bytes copied so far every few megabytes
+fn copy_hashing(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    hashers: &mut [&mut Sha256],
+    mut progress: impl FnMut(u64),
+) -> io::Result<u64> {
+    let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+    let mut copied = 0u64;
+    let mut next_report = PROGRESS_BYTES;
+    
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(copied),
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buffer[..read])?;
+        for hasher in hashers.iter_mut() {
+            hasher.update(&buffer[..read]);
+        }
+        copied += read as u64;
+        if copied >= next_report {
+            progress(copied);
+            next_report = copied + PROGRESS_BYTES;
+        }
+    }
+}
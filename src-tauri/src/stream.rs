@@ -0,0 +1,124 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::operations::OperationRegistry;
+use crate::{file_item, AppError, FileItem};
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+// A partial batch is sent anyway once it's this old, so slow network
+// directories still show entries as they trickle in
+const MAX_BATCH_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DirectoryEntries {
+    stream_id: String,
+    items: Vec<FileItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DirectoryStreamSummary {
+    stream_id: String,
+    total: usize,
+    cancelled: bool,
+}
+
+// Lists `path` without sorting, emitting entries in `dir-entry` batches as
+// they're read and a `dir-complete` event (the same summary as returned) at
+// the end. Item ids count up across batches in the order sent. Pass a
+// `stream_id` to be able to `cancel_operation` it.
+#[tauri::command]
+pub async fn stream_directory(
+    path: String,
+    include_hidden: Option<bool>,
+    batch_size: Option<usize>,
+    stream_id: Option<String>,
+    app_handle: AppHandle,
+    registry: tauri::State<'_, OperationRegistry>,
+) -> Result<DirectoryStreamSummary, AppError> {
+    let dir = PathBuf::from(&path);
+    
+    if !dir.is_dir() {
+        return Err(AppError::NotADirectory("Path is not a directory".to_string()));
+    }
+    
+    let include_hidden = include_hidden.unwrap_or(false);
+    let batch_size = batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    
+    let (stream_id, cancelled) = registry.register(stream_id, "stream")?;
+    
+    let walk_id = stream_id.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        stream_entries(&dir, include_hidden, batch_size, &walk_id, &cancelled, &app_handle)
+            .map(|(total, cancelled)| {
+                let summary = DirectoryStreamSummary { stream_id: walk_id, total, cancelled };
+                let _ = app_handle.emit("dir-complete", summary.clone());
+                summary
+            })
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("Directory stream failed: {}", e)));
+    
+    registry.finish(&stream_id);
+    
+    result?
+}
+
+fn stream_entries(
+    dir: &Path,
+    include_hidden: bool,
+    batch_size: usize,
+    stream_id: &str,
+    cancelled: &AtomicBool,
+    app_handle: &AppHandle,
+) -> Result<(usize, bool), AppError> {
+    let entries = fs::read_dir(dir).map_err(|e| AppError::io("Failed to read directory", e))?;
+    
+    let mut total = 0;
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut batch_started = Instant::now();
+    let send = |batch: &mut Vec<FileItem>| {
+        if batch.is_empty() {
+            return;
+        }
+        let _ = app_handle.emit("dir-entry", DirectoryEntries {
+            stream_id: stream_id.to_string(),
+            items: std::mem::take(batch),
+        });
+    };
+    
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if cancelled.load(Ordering::Relaxed) {
+            send(&mut batch);
+            return Ok((total, true));
+        }
+        
+        let entry_path = entry.path();
+        let metadata = match fs::symlink_metadata(&entry_path) {
+            Ok(meta) => meta,
+            Err(_) => continue,
+        };
+        
+        let item = file_item(&entry_path, &metadata, total.to_string());
+        if item.hidden && !include_hidden {
+            continue;
+        }
+        
+        if batch.is_empty() {
+            batch_started = Instant::now();
+        }
+        batch.push(item);
+        total += 1;
+        
+        if batch.len() >= batch_size || batch_started.elapsed() >= MAX_BATCH_DELAY {
+            send(&mut batch);
+        }
+    }
+    
+    send(&mut batch);
+    
+    Ok((total, false))
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::{file_item, write_atomic, AppError, FileItem};
+
+// User tags, persisted as one JSON object of canonical path -> sorted tags in
+// the app data folder. Renames and moves made through the app carry tags
+// along (`move_path`) and deletes drop them (`forget_path`); changes made
+// outside the app leave stale entries, which are skipped when searching.
+pub struct TagStore {
+    file: Option<PathBuf>, // None keeps tags in memory only
+    tags: Mutex<BTreeMap<String, Vec<String>>>,
+}
+
+impl TagStore {
+    pub fn open(file: Option<PathBuf>) -> Self {
+        let tags = file.as_ref()
+            .and_then(|file| fs::read(file).ok())
+            .and_then(|bytes| match serde_json::from_slice(&bytes) {
+                Ok(tags) => Some(tags),
+                Err(e) => {
+                    eprintln!("Ignoring unreadable tag store: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        
+        TagStore { file, tags: Mutex::new(tags) }
+    }
+    
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, BTreeMap<String, Vec<String>>>, AppError> {
+        self.tags.lock().map_err(|e| AppError::Internal(format!("Failed to lock tag store: {}", e)))
+    }
+    
+    fn save(&self, tags: &BTreeMap<String, Vec<String>>) -> Result<(), AppError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io("Failed to create tag store folder", e))?;
+        }
+        let json = serde_json::to_vec_pretty(tags)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize tags: {}", e)))?;
+        write_atomic(file, &json).map_err(|e| AppError::io("Failed to save tags", e))
+    }
+    
+    // Re-keys the tags of `from`, and of everything beneath it, to `to`. Call
+    // after a successful rename or move, with `from` canonicalized beforehand.
+    pub(crate) fn move_path(&self, from: Option<&Path>, to: &Path) {
+        let (Some(from), Ok(to)) = (from, fs::canonicalize(to)) else {
+            return;
+        };
+        if from == to {
+            return;
+        }
+        
+        self.update(|tags| {
+            let moved: Vec<String> = tags.keys()
+                .filter(|key| Path::new(key).starts_with(from))
+                .cloned()
+                .collect();
+            for key in &moved {
+                if let (Some(value), Ok(rest)) = (tags.remove(key), Path::new(key).strip_prefix(from)) {
+                    let new_key = if rest.as_os_str().is_empty() { to.clone() } else { to.join(rest) };
+                    tags.insert(new_key.to_string_lossy().to_string(), value);
+                }
+            }
+            !moved.is_empty()
+        });
+    }
+    
+    // Drops the tags of `path` and everything beneath it. Call after a
+    // delete, with `path` canonicalized beforehand.
+    pub(crate) fn forget_path(&self, path: &Path) {
+        self.update(|tags| {
+            let before = tags.len();
+            tags.retain(|key, _| !Path::new(key).starts_with(path));
+            tags.len() != before
+        });
+    }
+    
+    // Best-effort bookkeeping for other commands: a failure here must not fail
+    // the move or delete that already happened
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, Vec<String>>) -> bool) {
+        let Ok(mut tags) = self.lock() else {
+            return;
+        };
+        if change(&mut tags) {
+            if let Err(e) = self.save(&tags) {
+                eprintln!("Failed to update tag store: {}", e);
+            }
+        }
+    }
+}
+
+fn canonical_key(path: &str) -> Result<String, AppError> {
+    fs::canonicalize(path)
+        .map(|canonical| canonical.to_string_lossy().to_string())
+        .map_err(|e| AppError::io("Failed to resolve path", e))
+}
+
+// Replaces the item's tags. Tags are trimmed, deduplicated and sorted; an
+// empty list removes the item from the store. Returns the tags as stored.
+#[tauri::command]
+pub async fn set_tags(
+    path: String,
+    tags: Vec<String>,
+    store: tauri::State<'_, TagStore>,
+) -> Result<Vec<String>, AppError> {
+    let key = canonical_key(&path)?;
+    
+    let mut cleaned: Vec<String> = tags.iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    cleaned.sort();
+    cleaned.dedup();
+    
+    let mut all_tags = store.lock()?;
+    if cleaned.is_empty() {
+        all_tags.remove(&key);
+    } else {
+        all_tags.insert(key, cleaned.clone());
+    }
+    store.save(&all_tags)?;
+    
+    Ok(cleaned)
+}
+
+#[tauri::command]
+pub async fn get_tags(path: String, store: tauri::State<'_, TagStore>) -> Result<Vec<String>, AppError> {
+    let key = canonical_key(&path)?;
+    Ok(store.lock()?.get(&key).cloned().unwrap_or_default())
+}
+
+// Every tagged item under `root` (at any depth) carrying `tag`, in path order
+#[tauri::command]
+pub async fn find_by_tag(
+    root: String,
+    tag: String,
+    store: tauri::State<'_, TagStore>,
+) -> Result<Vec<FileItem>, AppError> {
+    let root = PathBuf::from(canonical_key(&root)?);
+    let all_tags = store.lock()?;
+    
+    let items = all_tags.iter()
+        .filter(|(key, tags)| Path::new(key).starts_with(&root) && tags.contains(&tag))
+        .filter_map(|(key, _)| {
+            let path = Path::new(key);
+            fs::symlink_metadata(path).ok().map(|metadata| (path, metadata))
+        })
+        .enumerate()
+        .map(|(index, (path, metadata))| file_item(path, &metadata, index.to_string()))
+        .collect();
+    
+    Ok(items)
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use notify::{EventKind, RecursiveMode, Watcher};
+use notify_debouncer_full::{new_debouncer, Debouncer, FileIdMap};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::AppError;
+
+const DEFAULT_TAIL_DEBOUNCE_MS: u64 = 100;
+// Appended data is emitted in pieces of at most this much
+const MAX_TAIL_CHUNK_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TailData {
+    tail_id: String,
+    content: String, // UTF-8, invalid bytes replaced
+    offset: u64,     // where `content` starts in the file
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TailReset {
+    tail_id: String,
+    size: u64, // the file's size now; data from offset 0 follows
+}
+
+// Tailed files keyed by path, which is also the `tail_id` on their events.
+// Kept apart from `WatcherState` so a tail and a folder watch on the same
+// path don't replace each other.
+#[derive(Default)]
+pub struct TailState(Mutex<HashMap<String, Debouncer<notify::RecommendedWatcher, FileIdMap>>>);
+
+// Follows a growing file: each time it grows, the new bytes are read from
+// where the last read stopped and emitted as `tail-data`. When the file
+// shrinks or is replaced (log rotation), `tail-reset` is emitted and reading
+// starts over from its beginning. Returns the offset following starts from,
+// the current size, so the caller can show what's there already with
+// `read_tail_lines`.
+#[tauri::command]
+pub async fn start_tail(
+    path: String,
+    debounce_ms: Option<u64>,
+    app_handle: AppHandle,
+    state: tauri::State<'_, TailState>,
+) -> Result<u64, AppError> {
+    let target = Path::new(&path);
+    if !target.is_file() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    // Through the parent folder, so a rotated-in replacement is still seen
+    let (parent, file_name) = match (target.parent(), target.file_name()) {
+        (Some(parent), Some(name)) if parent.as_os_str().is_empty() => (Path::new("."), name.to_os_string()),
+        (Some(parent), Some(name)) => (parent, name.to_os_string()),
+        _ => return Err(AppError::InvalidInput("Path has no file name".to_string())),
+    };
+    
+    let mut tails = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock tails: {}", e)))?;
+    tails.remove(&path);
+    
+    let start = fs::metadata(target).map_err(|e| AppError::io("Failed to read file metadata", e))?.len();
+    let mut offset = start;
+    let tail_id = path.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms.unwrap_or(DEFAULT_TAIL_DEBOUNCE_MS)),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            let Ok(events) = result else {
+                return;
+            };
+            let ours: Vec<_> = events.iter()
+                .filter(|event| event.paths.iter().any(|path| path.file_name() == Some(file_name.as_os_str())))
+                .collect();
+            if ours.is_empty() {
+                return;
+            }
+            
+            let path = Path::new(&tail_id);
+            let Ok(size) = fs::metadata(path).map(|metadata| metadata.len()) else {
+                return; // gone for now; a new file at the path resets
+            };
+            let replaced = ours.iter().any(|event| matches!(event.event.kind, EventKind::Create(_)));
+            if replaced || size < offset {
+                offset = 0;
+                let _ = app_handle.emit("tail-reset", TailReset { tail_id: tail_id.clone(), size });
+            }
+            
+            if let Err(e) = emit_appended(path, &tail_id, &mut offset, &app_handle) {
+                eprintln!("Failed to tail {}: {}", tail_id, e);
+            }
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    
+    debouncer.watcher().watch(parent, RecursiveMode::NonRecursive)
+        .map_err(|e| AppError::Io(format!("Failed to watch file: {}", e)))?;
+    tails.insert(path, debouncer);
+    
+    Ok(start)
+}
+
+#[tauri::command]
+pub async fn stop_tail(path: String, state: tauri::State<'_, TailState>) -> Result<(), AppError> {
+    let mut tails = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock tails: {}", e)))?;
+    tails.remove(&path);
+    Ok(())
+}
+
+// Reads from `offset` to the current end. A multibyte character cut off by
+// the end of what's been written so far is left for the next read.
+fn emit_appended(path: &Path, tail_id: &str, offset: &mut u64, app_handle: &AppHandle) -> std::io::Result<()> {
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut buffer = vec![0u8; MAX_TAIL_CHUNK_BYTES];
+    
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        
+        let bytes = &buffer[..read];
+        let complete = match std::str::from_utf8(bytes) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            _ => read,
+        };
+        if complete == 0 {
+            return Ok(());
+        }
+        
+        let _ = app_handle.emit("tail-data", TailData {
+            tail_id: tail_id.to_string(),
+            content: String::from_utf8_lossy(&bytes[..complete]).to_string(),
+            offset: *offset,
+        });
+        *offset += complete as u64;
+        
+        if complete < read {
+            if read < buffer.len() {
+                return Ok(());
+            }
+            // Only the buffer cut the character; pick it up again from there
+            file.seek(SeekFrom::Start(*offset))?;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::operation_log::OperationLog;
+use crate::{created_item, paths, write_atomic, AppError, FileItem};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Template {
+    id: String,   // the template's file name, for `create_from_template`
+    name: String, // without the extension
+    extension: Option<String>,
+    size: u64,
+}
+
+// Files to start new ones from, kept as plain files in a "templates" folder
+// of the app data folder, so users can add their own by dropping them in.
+pub struct TemplateStore {
+    dir: Option<PathBuf>, // None when the app data folder is unknown
+}
+
+impl TemplateStore {
+    pub fn open(dir: Option<PathBuf>) -> Self {
+        if let Some(dir) = &dir {
+            let _ = fs::create_dir_all(dir);
+        }
+        TemplateStore { dir }
+    }
+    
+    fn dir(&self) -> Result<&Path, AppError> {
+        self.dir.as_deref()
+            .ok_or_else(|| AppError::NotFound("Unable to determine the templates folder".to_string()))
+    }
+    
+    fn template_path(&self, template_id: &str) -> Result<PathBuf, AppError> {
+        // An id is a bare file name, so it can't reach outside the folder
+        if template_id.is_empty() || Path::new(template_id).file_name() != Some(template_id.as_ref()) {
+            return Err(AppError::InvalidInput(format!("Invalid template id '{}'", template_id)));
+        }
+        let path = self.dir()?.join(template_id);
+        if !path.is_file() {
+            return Err(AppError::NotFound(format!("No template '{}'", template_id)));
+        }
+        Ok(path)
+    }
+}
+
+// Where to put template files, so the UI can reveal it
+#[tauri::command]
+pub async fn get_templates_directory(store: tauri::State<'_, TemplateStore>) -> Result<String, AppError> {
+    Ok(store.dir()?.to_string_lossy().to_string())
+}
+
+// Every file in the templates folder by name. Hidden files and folders are
+// left out.
+#[tauri::command]
+pub async fn list_templates(store: tauri::State<'_, TemplateStore>) -> Result<Vec<Template>, AppError> {
+    let entries = fs::read_dir(store.dir()?).map_err(|e| AppError::io("Failed to read the templates folder", e))?;
+    
+    let mut templates: Vec<Template> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|entry| {
+            let metadata = fs::metadata(entry.path()).ok().filter(|metadata| metadata.is_file())?;
+            let path = entry.path();
+            Some(Template {
+                id: entry.file_name().to_string_lossy().to_string(),
+                name: path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                extension: path.extension().map(|ext| ext.to_string_lossy().to_string()),
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.id.cmp(&b.id)));
+    
+    Ok(templates)
+}
+
+// Creates `dir/name` from a template. Text templates (UTF-8) get their tokens
+// filled in: {{title}} (`title`, or the new name without its extension),
+// {{name}} (the new file name), {{date}} (2024-05-31), {{time}} (14:05) and
+// {{datetime}} (2024-05-31 14:05), all in local time. Anything else in braces
+// is left alone, and other templates are copied byte for byte.
+#[tauri::command]
+pub async fn create_from_template(
+    dir: String,
+    name: String,
+    template_id: String,
+    title: Option<String>,
+    store: tauri::State<'_, TemplateStore>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<FileItem, AppError> {
+    let base_path = Path::new(&dir);
+    
+    if !base_path.is_dir() {
+        return Err(AppError::NotFound("Directory does not exist".to_string()));
+    }
+    
+    let template = store.template_path(&template_id)?;
+    paths::check_name(&name, Some(base_path))?;
+    let file_path = base_path.join(&name);
+    
+    if file_path.exists() {
+        return Err(AppError::AlreadyExists("A file with that name already exists".to_string()));
+    }
+    
+    let bytes = fs::read(&template).map_err(|e| AppError::io("Failed to read template", e))?;
+    let content = match String::from_utf8(bytes) {
+        Ok(text) if !text.contains('\0') => {
+            let title = title.unwrap_or_else(|| {
+                Path::new(&name).file_stem().unwrap_or_default().to_string_lossy().to_string()
+            });
+            fill_tokens(&text, &title, &name).into_bytes()
+        }
+        Ok(text) => text.into_bytes(),
+        Err(e) => e.into_bytes(),
+    };
+    
+    let result = write_atomic(&file_path, &content).map_err(|e| AppError::io("Failed to create file", e));
+    log.record_result("create", &file_path, None, &result);
+    result?;
+    created_item(&file_path)
+}
+
+fn fill_tokens(text: &str, title: &str, name: &str) -> String {
+    let now = Local::now();
+    let date = now.format("%Y-%m-%d").to_string();
+    let time = now.format("%H:%M").to_string();
+    let datetime = format!("{} {}", date, time);
+    
+    // One pass, so a title that itself contains "{{date}}" stays as typed
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let token = after.find("}}").map(|end| (&after[..end], &after[end + 2..]));
+        let value = token.and_then(|(token, _)| match token.trim() {
+            "title" => Some(title),
+            "name" => Some(name),
+            "date" => Some(date.as_str()),
+            "time" => Some(time.as_str()),
+            "datetime" => Some(datetime.as_str()),
+            _ => None,
+        });
+        match (value, token) {
+            (Some(value), Some((_, remainder))) => {
+                filled.push_str(value);
+                rest = remainder;
+            }
+            _ => {
+                filled.push_str("{{");
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
@@ -0,0 +1,107 @@
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::{ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::AppError;
+
+const SUPPORTED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::WebP,
+    ImageFormat::Gif,
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Thumbnail {
+    data_url: String, // "data:image/png;base64,..."
+    width: u32,       // original image dimensions
+    height: u32,
+    cached: bool,
+}
+
+#[tauri::command]
+pub async fn generate_thumbnail(path: String, max_dimension: u32) -> Result<Thumbnail, AppError> {
+    tauri::async_runtime::spawn_blocking(move || thumbnail_for(Path::new(&path), max_dimension))
+        .await
+        .map_err(|e| AppError::Internal(format!("Thumbnail task failed: {}", e)))?
+}
+
+pub(crate) fn thumbnail_for(path: &Path, max_dimension: u32) -> Result<Thumbnail, AppError> {
+    if !path.exists() {
+        return Err(AppError::NotFound("File does not exist".to_string()));
+    }
+    
+    if path.is_dir() {
+        return Err(AppError::IsADirectory("Cannot generate a thumbnail for a directory".to_string()));
+    }
+    
+    if max_dimension == 0 {
+        return Err(AppError::InvalidInput("max_dimension must be greater than zero".to_string()));
+    }
+    
+    let reader = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| AppError::io("Failed to open image", e))?;
+    
+    match reader.format() {
+        Some(format) if SUPPORTED_FORMATS.contains(&format) => {}
+        _ => return Err(AppError::Unsupported("Unsupported image format".to_string())),
+    }
+    
+    // Cache hits only need the header for the original dimensions
+    let cache_path = cache_path_for(path, max_dimension)?;
+    if let Ok(png) = fs::read(&cache_path) {
+        let (width, height) = reader.into_dimensions()
+            .map_err(|e| AppError::Unsupported(format!("Failed to read image: {}", e)))?;
+        return Ok(Thumbnail { data_url: png_data_url(&png), width, height, cached: true });
+    }
+    
+    let image = reader.decode()
+        .map_err(|e| AppError::Unsupported(format!("Failed to decode image: {}", e)))?;
+    let (width, height) = (image.width(), image.height());
+    
+    // `thumbnail` keeps the aspect ratio; never scale small images up
+    let scaled = if width > max_dimension || height > max_dimension {
+        image.thumbnail(max_dimension, max_dimension)
+    } else {
+        image
+    };
+    
+    let mut png = Vec::new();
+    scaled.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| AppError::Internal(format!("Failed to encode thumbnail: {}", e)))?;
+    
+    // A failed cache write only costs a re-decode next time
+    if let Some(dir) = cache_path.parent() {
+        let _ = fs::create_dir_all(dir).and_then(|_| fs::write(&cache_path, &png));
+    }
+    
+    Ok(Thumbnail { data_url: png_data_url(&png), width, height, cached: false })
+}
+
+// Cache entries are keyed by path, mtime and requested size, so edits invalidate them
+fn cache_path_for(path: &Path, max_dimension: u32) -> Result<PathBuf, AppError> {
+    let metadata = fs::metadata(path).map_err(|e| AppError::io("Failed to read file metadata", e))?;
+    let modified = metadata.modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    
+    let mut hasher = Sha256::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(max_dimension.to_le_bytes());
+    
+    Ok(std::env::temp_dir()
+        .join("fileex-thumbnails")
+        .join(format!("{:x}.png", hasher.finalize())))
+}
+
+fn png_data_url(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", STANDARD.encode(png))
+}
@@ -0,0 +1,944 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::disks::available_space;
+use crate::paths::{display_path, extended_path};
+use crate::hashing::{hash_file, HashAlgorithm};
+use crate::operation_log::OperationLog;
+use crate::operations::OperationRegistry;
+use crate::tags::TagStore;
+use crate::undo::UndoJournal;
+use crate::{created_item, AppError, FileItem};
+
+// Files copied at once by `copy_items` unless told otherwise. Enough to hide
+// per-file latency on SSDs and network drives without thrashing a hard disk.
+const DEFAULT_COPY_CONCURRENCY: usize = 4;
+const MAX_COPY_CONCURRENCY: usize = 32;
+
+// What to do when an item already exists at the destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    Skip,
+    Overwrite, // Files are replaced, folders are merged child by child
+    Rename,    // Pick a free "name (1).ext" style name
+    Fail,      // Abort before touching anything if any item collides
+}
+
+impl ConflictPolicy {
+    pub fn parse(name: Option<&str>) -> Result<Self, AppError> {
+        match name.unwrap_or("skip") {
+            "skip" => Ok(ConflictPolicy::Skip),
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            "rename" => Ok(ConflictPolicy::Rename),
+            "fail" => Ok(ConflictPolicy::Fail),
+            other => Err(AppError::InvalidInput(format!(
+                "Unknown conflict policy '{}', expected \"skip\", \"overwrite\", \"rename\" or \"fail\"",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Copied,
+    Moved,
+    Overwritten,
+    Renamed,
+    Skipped,
+    Failed,
+    Cancelled, // not started, or stopped part-way with the source left in place
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferResult {
+    source: String,
+    destination: Option<String>,
+    status: TransferStatus,
+    error: Option<String>,
+    copy_fallback: bool, // moved by copy-then-delete because a rename crossed devices
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CopyProgress {
+    destination: String,
+    current_path: String,
+    bytes_copied: u64,
+    total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MoveProgress {
+    operation_id: String,
+    destination: String,
+    current_path: String,
+    items_done: usize,
+    total_items: usize,
+    bytes_copied: u64, // only cross-device moves copy anything
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CopyComplete {
+    destination: String,
+    bytes_copied: u64,
+    total_bytes: u64,
+    copied_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlannedAction {
+    Create,
+    Overwrite,
+    Rename,
+    Skip,
+    Reject(&'static str),
+}
+
+struct PlannedTransfer {
+    source: PathBuf,
+    destination: PathBuf,
+    action: PlannedAction,
+}
+
+impl PlannedTransfer {
+    fn performs_transfer(&self) -> bool {
+        !matches!(self.action, PlannedAction::Skip | PlannedAction::Reject(_))
+    }
+}
+
+// Resolves every source against the destination folder and the conflict
+// policy, in input order. Sources that can't be transferred at all are kept
+// as rejected entries so they still show up in the per-item results.
+// Destinations taken by earlier items count as conflicts too, so two sources
+// with one name never land on the same path.
+fn plan_transfers(
+    source_paths: &[String],
+    dest_path: &Path,
+    policy: ConflictPolicy,
+) -> Result<Vec<PlannedTransfer>, AppError> {
+    let mut planned = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut reserved = HashSet::new();
+    
+    for source_path in source_paths {
+        let source = PathBuf::from(source_path);
+        
+        let file_name = match source.file_name() {
+            Some(name) => name.to_os_string(),
+            None => {
+                planned.push(PlannedTransfer {
+                    source,
+                    destination: dest_path.to_path_buf(),
+                    action: PlannedAction::Reject("Source has no file name"),
+                });
+                continue;
+            }
+        };
+        
+        let destination = dest_path.join(&file_name);
+        
+        let rejection = if !source.exists() {
+            Some("Source does not exist")
+        } else if source.is_dir() && is_same_or_inside(dest_path, &source) {
+            Some("Cannot place a folder inside itself")
+        } else {
+            None
+        };
+        
+        if let Some(reason) = rejection {
+            planned.push(PlannedTransfer { source, destination, action: PlannedAction::Reject(reason) });
+            continue;
+        }
+        
+        let taken_in_batch = reserved.contains(&destination);
+        if !taken_in_batch && !destination.exists() {
+            reserved.insert(destination.clone());
+            planned.push(PlannedTransfer { source, destination, action: PlannedAction::Create });
+            continue;
+        }
+        
+        let (destination, action) = match policy {
+            ConflictPolicy::Skip => (destination, PlannedAction::Skip),
+            // Overwriting an earlier item of the same batch would lose it
+            ConflictPolicy::Overwrite if taken_in_batch => {
+                (destination, PlannedAction::Reject("Another item in this batch has the same name"))
+            }
+            ConflictPolicy::Overwrite if destination == source => (destination, PlannedAction::Skip),
+            ConflictPolicy::Overwrite => (destination, PlannedAction::Overwrite),
+            ConflictPolicy::Rename => (unique_destination(dest_path, &file_name, &reserved), PlannedAction::Rename),
+            ConflictPolicy::Fail => {
+                conflicts.push(destination.to_string_lossy().to_string());
+                continue;
+            }
+        };
+        
+        if !matches!(action, PlannedAction::Skip | PlannedAction::Reject(_)) {
+            reserved.insert(destination.clone());
+        }
+        planned.push(PlannedTransfer { source, destination, action });
+    }
+    
+    if !conflicts.is_empty() {
+        return Err(AppError::AlreadyExists(format!(
+            "Destination already contains: {}",
+            conflicts.join(", ")
+        )));
+    }
+    
+    Ok(planned)
+}
+
+fn is_same_or_inside(path: &Path, ancestor: &Path) -> bool {
+    match (fs::canonicalize(path), fs::canonicalize(ancestor)) {
+        (Ok(path), Ok(ancestor)) => path.starts_with(ancestor),
+        _ => false,
+    }
+}
+
+// "report.txt" -> "report (1).txt", "report (2).txt", ... until one is free,
+// both on disk and among the `reserved` paths
+pub(crate) fn unique_destination(dir: &Path, file_name: &std::ffi::OsStr, reserved: &HashSet<PathBuf>) -> PathBuf {
+    let name = Path::new(file_name);
+    let stem = name.file_stem().unwrap_or(file_name).to_string_lossy().to_string();
+    let extension = name.extension().map(|ext| ext.to_string_lossy().to_string());
+    
+    let mut counter = 1;
+    loop {
+        let candidate = match &extension {
+            Some(ext) => format!("{} ({}).{}", stem, counter, ext),
+            None => format!("{} ({})", stem, counter),
+        };
+        let path = dir.join(candidate);
+        if !path.exists() && !reserved.contains(&path) {
+            return path;
+        }
+        counter += 1;
+    }
+}
+
+impl TransferResult {
+    fn finished(planned: &PlannedTransfer, done: TransferStatus, result: Result<bool, std::io::Error>) -> Self {
+        let copy_fallback = matches!(result, Ok(true));
+        let (status, error) = match (result, planned.action) {
+            (Err(e), _) => (TransferStatus::Failed, Some(e.to_string())),
+            (Ok(_), PlannedAction::Reject(reason)) => (TransferStatus::Failed, Some(reason.to_string())),
+            (Ok(_), PlannedAction::Create) => (done, None),
+            (Ok(_), PlannedAction::Overwrite) => (TransferStatus::Overwritten, None),
+            (Ok(_), PlannedAction::Rename) => (TransferStatus::Renamed, None),
+            (Ok(_), PlannedAction::Skip) => (TransferStatus::Skipped, None),
+        };
+        
+        TransferResult {
+            source: planned.source.to_string_lossy().to_string(),
+            destination: Some(planned.destination.to_string_lossy().to_string()),
+            status,
+            error,
+            copy_fallback,
+        }
+    }
+    
+    fn cancelled(planned: &PlannedTransfer) -> Self {
+        TransferResult {
+            source: planned.source.to_string_lossy().to_string(),
+            destination: Some(planned.destination.to_string_lossy().to_string()),
+            status: TransferStatus::Cancelled,
+            error: None,
+            copy_fallback: false,
+        }
+    }
+}
+
+// What a real run would report, built from the same plan: rejected sources
+// show as failed and conflicts as overwritten, renamed or skipped
+fn dry_run_results(planned: &[PlannedTransfer], done: TransferStatus) -> Vec<TransferResult> {
+    planned.iter()
+        .map(|item| TransferResult::finished(item, done, Ok(false)))
+        .collect()
+}
+
+// "report.txt" -> "report copy.txt", "report copy 2.txt", ... like Finder.
+// Folders never get a split extension, so "v1.2" becomes "v1.2 copy".
+fn duplicate_destination(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let file_name = path.file_name()?;
+    let name = Path::new(file_name);
+    
+    let (stem, extension) = match name.extension() {
+        Some(ext) if !path.is_dir() => (
+            name.file_stem().unwrap_or(file_name).to_string_lossy().to_string(),
+            format!(".{}", ext.to_string_lossy()),
+        ),
+        _ => (file_name.to_string_lossy().to_string(), String::new()),
+    };
+    
+    let mut counter = 1;
+    loop {
+        let candidate = match counter {
+            1 => format!("{} copy{}", stem, extension),
+            n => format!("{} copy {}{}", stem, n, extension),
+        };
+        let destination = dir.join(candidate);
+        if !destination.exists() {
+            return Some(destination);
+        }
+        counter += 1;
+    }
+}
+
+#[tauri::command]
+pub async fn duplicate_item(path: String, log: tauri::State<'_, OperationLog>) -> Result<FileItem, AppError> {
+    let source = Path::new(&path);
+    
+    if !source.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    
+    let destination = duplicate_destination(source)
+        .ok_or_else(|| AppError::InvalidInput("Cannot duplicate this item".to_string()))?;
+    
+    let result = copy_path(source, &destination, &mut CopyProgressTracker::silent());
+    log.record("copy", source, Some(&destination), result.as_ref().err().map(|e| e.to_string()));
+    if let Err(e) = result {
+        let _ = if destination.is_dir() { fs::remove_dir_all(&destination) } else { fs::remove_file(&destination) };
+        return Err(AppError::io("Failed to duplicate item", e));
+    }
+    
+    created_item(&destination)
+}
+
+// Folders are created up front, one item after another, then the files are
+// copied by `max_concurrency` threads (1 copies serially, best on spinning
+// disks). Results stay in input order; an item fails with the first error
+// among its files.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn copy_items(
+    source_paths: Vec<String>,
+    destination_path: String,
+    on_conflict: Option<String>,
+    dry_run: Option<bool>,
+    verify: Option<bool>,
+    preserve_mtime: Option<bool>,
+    max_concurrency: Option<usize>,
+    app_handle: AppHandle,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<Vec<TransferResult>, AppError> {
+    let dest_path = Path::new(&destination_path);
+    
+    if !dest_path.exists() || !dest_path.is_dir() {
+        return Err(AppError::NotFound("Destination directory does not exist".to_string()));
+    }
+    
+    let policy = ConflictPolicy::parse(on_conflict.as_deref())?;
+    
+    // Resolve what will actually be copied up front so the total is accurate
+    let planned = plan_transfers(&source_paths, dest_path, policy)?;
+    
+    let total_bytes = planned.iter()
+        .filter(|p| p.performs_transfer())
+        .map(|p| path_size(&p.source))
+        .sum();
+    
+    // Refuse up front rather than fail half-way. Overwrites free space as
+    // they go, so only pure additions can be judged this simply.
+    let overwrites = planned.iter().any(|p| p.action == PlannedAction::Overwrite);
+    if let Some(available) = available_space(dest_path).filter(|_| !overwrites) {
+        if total_bytes > available {
+            return Err(AppError::Io(format!(
+                "Not enough free space at the destination: {} bytes needed, {} available",
+                total_bytes, available
+            )));
+        }
+    }
+    
+    if dry_run.unwrap_or(false) {
+        return Ok(dry_run_results(&planned, TransferStatus::Copied));
+    }
+    
+    let progress = CopyProgressTracker {
+        events: ProgressEvents::Copy(&app_handle),
+        destination: destination_path.clone(),
+        bytes_copied: 0,
+        total_bytes,
+        preserve_mtime: preserve_mtime.unwrap_or(false),
+        verify: verify.unwrap_or(false),
+        cancelled: None,
+    };
+    
+    // Folders first, serially, so every file's parent exists before any copy
+    let mut errors: Vec<Option<std::io::Error>> = planned.iter().map(|_| None).collect();
+    let mut jobs = Vec::new();
+    let mut folders = Vec::new();
+    for (index, item) in planned.iter().enumerate().filter(|(_, item)| item.performs_transfer()) {
+        if let Err(e) = plan_copy_tree(&item.source, &item.destination, index, &mut jobs, &mut folders) {
+            jobs.retain(|job: &FileJob| job.item != index);
+            errors[index] = Some(e);
+        }
+    }
+    
+    let concurrency = max_concurrency.unwrap_or(DEFAULT_COPY_CONCURRENCY).clamp(1, MAX_COPY_CONCURRENCY);
+    let progress = Mutex::new(progress);
+    for (job, error) in run_copy_jobs(&jobs, concurrency, &progress) {
+        let item_error = &mut errors[jobs[job].item];
+        if item_error.is_none() {
+            *item_error = Some(error);
+        }
+    }
+    let progress = progress.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    
+    // Copying the files touched each folder's mtime, so restore them last,
+    // deepest first
+    if progress.preserve_mtime {
+        for (item, src, dst) in folders.iter().rev() {
+            if errors[*item].is_none() {
+                let restored = fs::metadata(src).and_then(|meta| {
+                    filetime::set_file_mtime(dst, filetime::FileTime::from_last_modification_time(&meta))
+                });
+                if let Err(e) = restored {
+                    errors[*item] = Some(e);
+                }
+            }
+        }
+    }
+    
+    let results: Vec<TransferResult> = planned.iter()
+        .zip(errors)
+        .map(|(item, error)| {
+            TransferResult::finished(item, TransferStatus::Copied, error.map_or(Ok(false), Err))
+        })
+        .collect();
+    for (item, result) in planned.iter().zip(&results).filter(|(item, _)| item.performs_transfer()) {
+        log.record("copy", &item.source, Some(&item.destination), result.error.clone());
+    }
+    
+    let copied_count = results.iter()
+        .filter(|r| !matches!(r.status, TransferStatus::Skipped | TransferStatus::Failed))
+        .count();
+    
+    let _ = app_handle.emit("copy-complete", CopyComplete {
+        destination: destination_path,
+        bytes_copied: progress.bytes_copied,
+        total_bytes,
+        copied_count,
+    });
+    
+    Ok(results)
+}
+
+enum ProgressEvents<'a> {
+    Silent,
+    Copy(&'a AppHandle),
+    Move {
+        app_handle: &'a AppHandle,
+        operation_id: String,
+        items_done: usize,
+        total_items: usize,
+    },
+}
+
+struct CopyProgressTracker<'a> {
+    events: ProgressEvents<'a>,
+    destination: String,
+    bytes_copied: u64,
+    total_bytes: u64,
+    preserve_mtime: bool,
+    verify: bool, // re-read and hash both sides of every copied file
+    cancelled: Option<&'a AtomicBool>, // checked before each file
+}
+
+impl CopyProgressTracker<'_> {
+    // For undo and duplicates: no events, timestamps preserved
+    fn silent() -> Self {
+        CopyProgressTracker {
+            events: ProgressEvents::Silent,
+            destination: String::new(),
+            bytes_copied: 0,
+            total_bytes: 0,
+            preserve_mtime: true,
+            verify: false,
+            cancelled: None,
+        }
+    }
+    
+    fn check_cancelled(&self) -> Result<(), std::io::Error> {
+        match self.cancelled {
+            Some(flag) if flag.load(Ordering::Relaxed) => {
+                Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Cancelled"))
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    fn file_copied(&mut self, path: &Path, bytes: u64) {
+        self.bytes_copied += bytes;
+        self.report(path);
+    }
+    
+    fn report(&self, path: &Path) {
+        match &self.events {
+            ProgressEvents::Silent => {}
+            ProgressEvents::Copy(app_handle) => {
+                let _ = app_handle.emit("copy-progress", CopyProgress {
+                    destination: self.destination.clone(),
+                    current_path: display_path(path),
+                    bytes_copied: self.bytes_copied,
+                    total_bytes: self.total_bytes,
+                });
+            }
+            ProgressEvents::Move { app_handle, operation_id, items_done, total_items } => {
+                let _ = app_handle.emit("move-progress", MoveProgress {
+                    operation_id: operation_id.clone(),
+                    destination: self.destination.clone(),
+                    current_path: display_path(path),
+                    items_done: *items_done,
+                    total_items: *total_items,
+                    bytes_copied: self.bytes_copied,
+                });
+            }
+        }
+    }
+}
+
+// Bytes that copying `path` will write. Symlinks are copied as links, so
+// they add nothing and are never followed, which also keeps a link back up
+// the tree from looping.
+fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if metadata.file_type().is_symlink() {
+        return 0;
+    }
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    
+    match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| path_size(&entry.path()))
+            .sum(),
+        Err(_) => 0,
+    }
+}
+
+// Copies a file or folder to `dst`. An existing folder at `dst` is merged into;
+// an existing entry of the other kind is removed first. Symlinks, at the top
+// or anywhere below, are copied as links rather than followed.
+fn copy_path(src: &Path, dst: &Path, progress: &mut CopyProgressTracker) -> Result<(), std::io::Error> {
+    let (src, dst) = (&extended_path(src), &extended_path(dst));
+    if is_real_dir(src) {
+        if dst.exists() && !dst.is_dir() {
+            fs::remove_file(dst)?;
+        }
+        copy_dir_recursive(src, dst, progress)
+    } else {
+        if dst.is_dir() {
+            fs::remove_dir_all(dst)?;
+        }
+        copy_file_with_progress(src, dst, progress)
+    }
+}
+
+fn copy_file_with_progress(
+    src: &Path,
+    dst: &Path,
+    progress: &mut CopyProgressTracker,
+) -> Result<(), std::io::Error> {
+    progress.check_cancelled()?;
+    let bytes = copy_file(src, dst, progress.preserve_mtime, progress.verify)?;
+    progress.file_copied(src, bytes);
+    Ok(())
+}
+
+fn copy_file(src: &Path, dst: &Path, preserve_mtime: bool, verify: bool) -> Result<u64, std::io::Error> {
+    if fs::symlink_metadata(src)?.file_type().is_symlink() {
+        copy_link(src, dst)?;
+        return Ok(0);
+    }
+    
+    let bytes = fs::copy(src, dst)?;
+    
+    // `fs::copy` keeps permissions but resets the modification time
+    if preserve_mtime {
+        let modified = fs::metadata(src)?.modified()?;
+        fs::File::options().write(true).open(dst)?.set_modified(modified)?;
+    }
+    
+    if verify {
+        verify_copy(src, dst)?;
+    }
+    
+    Ok(bytes)
+}
+
+// A folder itself, not a symlink to one
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir())
+}
+
+// Recreates the link at `dst` with the same target, relative targets
+// included, replacing whatever is there
+fn copy_link(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
+    let target = fs::read_link(src)?;
+    match fs::symlink_metadata(dst) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(dst)?,
+        Ok(_) => fs::remove_file(dst)?,
+        Err(_) => {}
+    }
+    make_link(&target, src, dst)
+}
+
+#[cfg(unix)]
+fn make_link(target: &Path, _src: &Path, dst: &Path) -> Result<(), std::io::Error> {
+    std::os::unix::fs::symlink(target, dst)
+}
+
+// Windows links are made as file or folder links; a broken one stays a file link
+#[cfg(windows)]
+fn make_link(target: &Path, src: &Path, dst: &Path) -> Result<(), std::io::Error> {
+    if fs::metadata(src).is_ok_and(|meta| meta.is_dir()) {
+        std::os::windows::fs::symlink_dir(target, dst)
+    } else {
+        std::os::windows::fs::symlink_file(target, dst)
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn make_link(_target: &Path, _src: &Path, _dst: &Path) -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "Symlinks are not supported on this platform"))
+}
+
+// One file of a `copy_items` item, tagged with the item's index
+struct FileJob {
+    item: usize,
+    src: PathBuf,
+    dst: PathBuf,
+}
+
+// Does everything `copy_path` does except copy file contents: folders are
+// created (and conflicting entries removed) now, files become jobs. Folders
+// are listed parents first, with the index of the item they belong to.
+fn plan_copy_tree(
+    src: &Path,
+    dst: &Path,
+    item: usize,
+    jobs: &mut Vec<FileJob>,
+    folders: &mut Vec<(usize, PathBuf, PathBuf)>,
+) -> Result<(), std::io::Error> {
+    let (src, dst) = (&extended_path(src), &extended_path(dst));
+    // Symlinks become jobs too, and `copy_file` recreates them as links
+    if !is_real_dir(src) {
+        if dst.is_dir() {
+            fs::remove_dir_all(dst)?;
+        }
+        jobs.push(FileJob { item, src: src.to_path_buf(), dst: dst.to_path_buf() });
+        return Ok(());
+    }
+    
+    if dst.exists() && !dst.is_dir() {
+        fs::remove_file(dst)?;
+    }
+    fs::create_dir_all(dst)?;
+    folders.push((item, src.to_path_buf(), dst.to_path_buf()));
+    
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        plan_copy_tree(&entry.path(), &dst.join(entry.file_name()), item, jobs, folders)?;
+    }
+    Ok(())
+}
+
+// Copies every job's file using `concurrency` threads pulling from a shared
+// cursor. Returns the failures as (job index, error).
+fn run_copy_jobs(
+    jobs: &[FileJob],
+    concurrency: usize,
+    progress: &Mutex<CopyProgressTracker>,
+) -> Vec<(usize, std::io::Error)> {
+    let (preserve_mtime, verify) = match progress.lock() {
+        Ok(progress) => (progress.preserve_mtime, progress.verify),
+        Err(_) => (false, false),
+    };
+    let next = AtomicUsize::new(0);
+    
+    let worker = || {
+        let mut failures = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(job) = jobs.get(index) else {
+                return failures;
+            };
+            match copy_file(&job.src, &job.dst, preserve_mtime, verify) {
+                Ok(bytes) => {
+                    if let Ok(mut progress) = progress.lock() {
+                        progress.file_copied(&job.src, bytes);
+                    }
+                }
+                Err(e) => failures.push((index, e)),
+            }
+        }
+    };
+    
+    if concurrency == 1 || jobs.len() < 2 {
+        return worker();
+    }
+    
+    let mut failures: Vec<(usize, std::io::Error)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..concurrency.min(jobs.len())).map(|_| scope.spawn(worker)).collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap_or_default()).collect()
+    });
+    // Earlier files first, so "the first error" of an item doesn't depend on timing
+    failures.sort_by_key(|(index, _)| *index);
+    failures
+}
+
+// `fs::copy` succeeding doesn't prove the bytes arrived intact on flaky
+// network drives. A mismatched copy is removed so it can't pass for good.
+fn verify_copy(src: &Path, dst: &Path) -> Result<(), std::io::Error> {
+    let hash = |path: &Path| {
+        hash_file(path, HashAlgorithm::Blake3).map_err(|e| std::io::Error::other(e.to_string()))
+    };
+    
+    if hash(src)? != hash(dst)? {
+        let _ = fs::remove_file(dst);
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Verification failed: copy of {} does not match the source", src.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    progress: &mut CopyProgressTracker,
+) -> Result<(), std::io::Error> {
+    fs::create_dir_all(dst)?;
+    
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        copy_path(&entry.path(), &dst.join(entry.file_name()), progress)?;
+    }
+    
+    // Last, as copying the children touched the folder's own mtime
+    if progress.preserve_mtime {
+        let modified = filetime::FileTime::from_last_modification_time(&fs::metadata(src)?);
+        filetime::set_file_mtime(dst, modified)?;
+    }
+    
+    Ok(())
+}
+
+// Emits `move-progress` after each item, and per file while a cross-device
+// move copies. Pass an `operation_id` to be able to `cancel_operation` it
+// (every progress event carries the id either way). Items moved before the
+// cancel stay moved; the one in progress keeps its source and the rest are
+// reported as `cancelled`.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn move_items(
+    source_paths: Vec<String>,
+    destination_path: String,
+    on_conflict: Option<String>,
+    dry_run: Option<bool>,
+    operation_id: Option<String>,
+    app_handle: AppHandle,
+    tags: tauri::State<'_, TagStore>,
+    journal: tauri::State<'_, UndoJournal>,
+    registry: tauri::State<'_, OperationRegistry>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<Vec<TransferResult>, AppError> {
+    let dest_path = Path::new(&destination_path);
+    
+    if !dest_path.exists() || !dest_path.is_dir() {
+        return Err(AppError::NotFound("Destination directory does not exist".to_string()));
+    }
+    
+    let policy = ConflictPolicy::parse(on_conflict.as_deref())?;
+    let planned = plan_transfers(&source_paths, dest_path, policy)?;
+    
+    if dry_run.unwrap_or(false) {
+        return Ok(dry_run_results(&planned, TransferStatus::Moved));
+    }
+    
+    let (operation_id, cancelled) = registry.register(operation_id, "move")?;
+    let mut progress = CopyProgressTracker {
+        events: ProgressEvents::Move {
+            app_handle: &app_handle,
+            operation_id: operation_id.clone(),
+            items_done: 0,
+            total_items: planned.len(),
+        },
+        destination: destination_path.clone(),
+        bytes_copied: 0,
+        total_bytes: 0,
+        preserve_mtime: true,
+        verify: false,
+        cancelled: Some(&cancelled),
+    };
+    let mut results = Vec::with_capacity(planned.len());
+    
+    for item in &planned {
+        if cancelled.load(Ordering::Relaxed) {
+            results.push(TransferResult::cancelled(item));
+            continue;
+        }
+        
+        let canonical = fs::canonicalize(&item.source).ok();
+        let result = match item.action {
+            PlannedAction::Skip | PlannedAction::Reject(_) => Ok(false),
+            PlannedAction::Overwrite => move_over(&item.source, &item.destination, &mut progress),
+            PlannedAction::Create | PlannedAction::Rename => {
+                rename_or_copy_with(&item.source, &item.destination, &mut progress)
+            }
+        };
+        
+        match &result {
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted && cancelled.load(Ordering::Relaxed) => {
+                results.push(TransferResult::cancelled(item));
+                continue;
+            }
+            Ok(_) if item.performs_transfer() => tags.move_path(canonical.as_deref(), &item.destination),
+            _ => {}
+        }
+        if item.performs_transfer() {
+            log.record("move", &item.source, Some(&item.destination), result.as_ref().err().map(|e| e.to_string()));
+        }
+        results.push(TransferResult::finished(item, TransferStatus::Moved, result));
+        
+        if let ProgressEvents::Move { items_done, .. } = &mut progress.events {
+            *items_done += 1;
+        }
+        progress.report(&item.source);
+    }
+    
+    registry.finish(&operation_id);
+    
+    // Overwrites aren't undoable: whatever was replaced is gone
+    let undoable: Vec<(PathBuf, PathBuf)> = planned.iter()
+        .zip(&results)
+        .filter(|(_, result)| matches!(result.status, TransferStatus::Moved | TransferStatus::Renamed))
+        .map(|(item, _)| (item.source.clone(), item.destination.clone()))
+        .collect();
+    journal.record_moves(format!("Move {} items to {}", undoable.len(), destination_path), undoable);
+    
+    Ok(results)
+}
+
+// Moves and renames in one go: `new_path` is the full destination, in any
+// folder, and must not exist yet. Within one volume it's a single atomic
+// rename; across volumes it's copied then deleted, like `move_items`.
+#[tauri::command]
+pub async fn rename_to(
+    old_path: String,
+    new_path: String,
+    tags: tauri::State<'_, TagStore>,
+    journal: tauri::State<'_, UndoJournal>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<String, AppError> {
+    let source = Path::new(&old_path);
+    let destination = Path::new(&new_path);
+    
+    if fs::symlink_metadata(source).is_err() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    let (Some(parent), Some(name)) = (destination.parent(), destination.file_name()) else {
+        return Err(AppError::InvalidInput("Destination has no file name".to_string()));
+    };
+    let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+    if !parent.is_dir() {
+        return Err(AppError::NotFound("Destination folder does not exist".to_string()));
+    }
+    crate::paths::check_name(&name.to_string_lossy(), Some(parent))?;
+    
+    if fs::symlink_metadata(destination).is_ok() {
+        return Err(AppError::AlreadyExists("An item already exists at the destination".to_string()));
+    }
+    if source.is_dir() && is_same_or_inside(parent, source) {
+        return Err(AppError::InvalidInput("Cannot move a folder into itself".to_string()));
+    }
+    
+    let canonical = fs::canonicalize(source).ok();
+    let result = rename_or_copy(source, destination);
+    log.record("move", source, Some(destination), result.as_ref().err().map(|e| e.to_string()));
+    
+    let copied = result.map_err(|e| AppError::io("Failed to move item", e))?;
+    tags.move_path(canonical.as_deref(), destination);
+    journal.record_moves(
+        format!("Move {} to {}", source.display(), destination.display()),
+        vec![(source.to_path_buf(), destination.to_path_buf())],
+    );
+    
+    Ok(if copied {
+        format!("Item copied to '{}' and the original removed", new_path)
+    } else {
+        format!("Item moved to '{}'", new_path)
+    })
+}
+
+// Moves `src` onto an existing `dst`: folders are merged child by child and
+// the emptied source folder removed, anything else replaces what's there.
+// Returns whether any part of the move needed the copy fallback.
+fn move_over(src: &Path, dst: &Path, progress: &mut CopyProgressTracker) -> Result<bool, std::io::Error> {
+    let (src, dst) = (&extended_path(src), &extended_path(dst));
+    if is_real_dir(src) && dst.is_dir() {
+        let mut copied = false;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let child_dst = dst.join(entry.file_name());
+            copied |= if child_dst.exists() {
+                move_over(&entry.path(), &child_dst, progress)?
+            } else {
+                rename_or_copy_with(&entry.path(), &child_dst, progress)?
+            };
+        }
+        fs::remove_dir(src)?;
+        return Ok(copied);
+    }
+    
+    if dst.is_dir() {
+        fs::remove_dir_all(dst)?;
+    } else if is_real_dir(src) {
+        fs::remove_file(dst)?;
+    }
+    
+    // Renaming a file over another file replaces it
+    rename_or_copy_with(src, dst, progress)
+}
+
+// Renames `src` to `dst`, falling back to copy-then-delete when they're on
+// different filesystems. Returns true when the fallback was used. If the copy
+// fails part-way the partial destination is removed and the source kept.
+pub(crate) fn rename_or_copy(src: &Path, dst: &Path) -> Result<bool, std::io::Error> {
+    rename_or_copy_with(src, dst, &mut CopyProgressTracker::silent())
+}
+
+// The same, reporting the fallback copy's progress and stopping it on cancel
+fn rename_or_copy_with(src: &Path, dst: &Path, progress: &mut CopyProgressTracker) -> Result<bool, std::io::Error> {
+    let (src, dst) = (&extended_path(src), &extended_path(dst));
+    match fs::rename(src, dst) {
+        Ok(_) => return Ok(false),
+        Err(e) if e.kind() != std::io::ErrorKind::CrossesDevices => return Err(e),
+        Err(_) => {}
+    }
+    
+    if let Err(e) = copy_path(src, dst, progress) {
+        let _ = if dst.is_dir() { fs::remove_dir_all(dst) } else { fs::remove_file(dst) };
+        return Err(e);
+    }
+    
+    let removed = if src.is_dir() { fs::remove_dir_all(src) } else { fs::remove_file(src) };
+    removed.map_err(|e| {
+        std::io::Error::new(e.kind(), format!("Copied to destination but failed to remove source: {}", e))
+    })?;
+    
+    Ok(true)
+}
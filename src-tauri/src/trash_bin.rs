@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::operation_log::OperationLog;
+use crate::undo::UndoJournal;
+use crate::{describe_trash_error, AppError};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrashedItem {
+    original_path: String,
+    // Handle for `restore_from_trash`; None when the item was trashed but the
+    // platform gave no way to find it again
+    trash_id: Option<String>,
+    error: Option<String>,
+}
+
+// Where each item trashed this session went, keyed by trash id. Only macOS
+// needs it: there is no API to list the trash, so ids are only good until
+// the app quits. Windows and Linux ids come from the system trash and
+// stay valid across restarts.
+#[derive(Default)]
+pub struct TrashLedger(Mutex<HashMap<String, PathBuf>>);
+
+// Everything in the trash: top-level items (a trashed folder is one) and
+// their total size on disk
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TrashSummary {
+    items: usize,
+    bytes: u64,
+}
+
+#[tauri::command]
+pub async fn trash_items(
+    paths: Vec<String>,
+    ledger: tauri::State<'_, TrashLedger>,
+    journal: tauri::State<'_, UndoJournal>,
+    log: tauri::State<'_, OperationLog>,
+) -> Result<Vec<TrashedItem>, AppError> {
+    let mut results = Vec::with_capacity(paths.len());
+    let mut trashed = Vec::new();
+    
+    for path in paths {
+        let result = fs::canonicalize(&path)
+            .map_err(|e| AppError::io("Failed to resolve path", e).to_string())
+            .and_then(|original| trash_one(&original, &ledger).map(|trash_id| (original, trash_id)));
+        log.record("trash", Path::new(&path), None, result.as_ref().err().cloned());
+        
+        results.push(match result {
+            Ok((original, trash_id)) => {
+                trashed.push((original, trash_id.clone()));
+                TrashedItem { original_path: path, trash_id, error: None }
+            }
+            Err(error) => TrashedItem { original_path: path, trash_id: None, error: Some(error) },
+        });
+    }
+    
+    journal.record_trash(format!("Trash {} items", trashed.len()), trashed);
+    
+    Ok(results)
+}
+
+// Puts the item back where it was. Fails with `already_exists` instead of
+// overwriting if something has taken its place since. Returns the restored path.
+#[tauri::command]
+pub async fn restore_from_trash(
+    trash_id: String,
+    ledger: tauri::State<'_, TrashLedger>,
+) -> Result<String, AppError> {
+    restore_one(&trash_id, &ledger).map(|path| path.to_string_lossy().to_string())
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+pub(crate) fn trash_one(original: &Path, _ledger: &TrashLedger) -> Result<Option<String>, String> {
+    trash::delete(original).map_err(|e| describe_trash_error(&e))?;
+    
+    // The system trash doesn't report what it created, so look it up by its
+    // original path, newest first in case it was trashed before
+    let item = trash::os_limited::list()
+        .ok()
+        .and_then(|items| {
+            items.into_iter()
+                .filter(|item| item.original_path() == original)
+                .max_by_key(|item| item.time_deleted)
+        });
+    
+    Ok(item.map(|item| item.id.to_string_lossy().to_string()))
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+pub(crate) fn restore_one(trash_id: &str, _ledger: &TrashLedger) -> Result<PathBuf, AppError> {
+    let items = trash::os_limited::list()
+        .map_err(|e| AppError::Io(format!("Failed to read the trash: {}", e)))?;
+    let item = items.into_iter()
+        .find(|item| item.id.to_string_lossy() == trash_id)
+        .ok_or_else(|| AppError::NotFound("Item is no longer in the trash".to_string()))?;
+    let original = item.original_path();
+    
+    if original.exists() {
+        return Err(AppError::AlreadyExists("Something already exists at the original location".to_string()));
+    }
+    
+    trash::os_limited::restore_all([item])
+        .map_err(|e| AppError::Io(format!("Failed to restore item: {}", e)))?;
+    Ok(original)
+}
+
+// Moving into ~/.Trash ourselves is what makes the item findable again.
+// Items on other volumes can't be renamed there and go through Finder
+// instead, so they come back without a trash id.
+#[cfg(target_os = "macos")]
+pub(crate) fn trash_one(original: &Path, ledger: &TrashLedger) -> Result<Option<String>, String> {
+    let file_name = original.file_name()
+        .ok_or_else(|| "Cannot move a root directory to the trash".to_string())?;
+    let trash_dir = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".Trash"));
+    
+    if let Some(trash_dir) = trash_dir.filter(|dir| dir.is_dir()) {
+        let destination = crate::transfer::unique_destination(&trash_dir, file_name, &Default::default());
+        if fs::rename(original, &destination).is_ok() {
+            let trash_id = destination.to_string_lossy().to_string();
+            if let Ok(mut entries) = ledger.0.lock() {
+                entries.insert(trash_id.clone(), original.to_path_buf());
+            }
+            return Ok(Some(trash_id));
+        }
+    }
+    
+    trash::delete(original).map_err(|e| describe_trash_error(&e))?;
+    Ok(None)
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn restore_one(trash_id: &str, ledger: &TrashLedger) -> Result<PathBuf, AppError> {
+    let mut entries = ledger.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock trash ledger: {}", e)))?;
+    let original = entries.get(trash_id)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound("Unknown trash id; only items trashed this session can be restored".to_string()))?;
+    
+    if original.exists() {
+        return Err(AppError::AlreadyExists("Something already exists at the original location".to_string()));
+    }
+    
+    fs::rename(trash_id, &original).map_err(|e| AppError::io("Failed to restore item", e))?;
+    entries.remove(trash_id);
+    Ok(original)
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+pub(crate) fn trash_one(original: &Path, _ledger: &TrashLedger) -> Result<Option<String>, String> {
+    trash::delete(original).map_err(|e| describe_trash_error(&e))?;
+    Ok(None)
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+pub(crate) fn restore_one(_trash_id: &str, _ledger: &TrashLedger) -> Result<PathBuf, AppError> {
+    Err(AppError::Unsupported("Restoring from the trash is not supported on this platform".to_string()))
+}
+
+// What's in the trash. On macOS that's ~/.Trash only; the per-volume trashes
+// of external drives aren't counted, and reading ~/.Trash needs Full Disk
+// Access on recent versions.
+#[tauri::command]
+pub async fn get_trash_size() -> Result<TrashSummary, AppError> {
+    tauri::async_runtime::spawn_blocking(trash_summary)
+        .await
+        .map_err(|e| AppError::Internal(format!("Trash size task failed: {}", e)))?
+}
+
+// Permanently deletes everything in the trash and returns what was removed.
+// On macOS this asks Finder to empty the trash, which may show its own
+// confirmation and covers every volume, while the counts are of ~/.Trash.
+#[tauri::command]
+pub async fn empty_trash(ledger: tauri::State<'_, TrashLedger>) -> Result<TrashSummary, AppError> {
+    let removed = tauri::async_runtime::spawn_blocking(empty_system_trash)
+        .await
+        .map_err(|e| AppError::Internal(format!("Empty trash task failed: {}", e)))??;
+    
+    // Nothing the ledger points at exists any more
+    if let Ok(mut entries) = ledger.0.lock() {
+        entries.clear();
+    }
+    Ok(removed)
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn trash_summary() -> Result<TrashSummary, AppError> {
+    let items = trash::os_limited::list()
+        .map_err(|e| AppError::Io(format!("Failed to read the trash: {}", e)))?;
+    Ok(summarize(&items))
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn empty_system_trash() -> Result<TrashSummary, AppError> {
+    let items = trash::os_limited::list()
+        .map_err(|e| AppError::Io(format!("Failed to read the trash: {}", e)))?;
+    let summary = summarize(&items);
+    
+    trash::os_limited::purge_all(items)
+        .map_err(|e| AppError::Io(format!("Failed to empty the trash: {}", e)))?;
+    Ok(summary)
+}
+
+// Sizes come from the trashed copies themselves: on Windows an item's id is
+// its path in the Recycle Bin, on Linux the id is its .trashinfo file and the
+// item sits under files/ with the same name
+#[cfg(any(windows, target_os = "linux"))]
+fn summarize(items: &[trash::TrashItem]) -> TrashSummary {
+    let stored = |item: &trash::TrashItem| -> Option<PathBuf> {
+        let id = Path::new(&item.id);
+        if cfg!(windows) {
+            return Some(id.to_path_buf());
+        }
+        Some(id.parent()?.parent()?.join("files").join(id.file_stem()?))
+    };
+    
+    TrashSummary {
+        items: items.len(),
+        bytes: items.iter().filter_map(stored).map(|path| stored_size(&path)).sum(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn user_trash() -> Result<PathBuf, AppError> {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".Trash"))
+        .ok_or_else(|| AppError::NotFound("Unable to determine home directory".to_string()))
+}
+
+#[cfg(target_os = "macos")]
+fn trash_summary() -> Result<TrashSummary, AppError> {
+    let entries = fs::read_dir(user_trash()?).map_err(|e| AppError::io("Failed to read the trash", e))?;
+    
+    let mut summary = TrashSummary::default();
+    // Finder's own bookkeeping file isn't a trashed item
+    for entry in entries.filter_map(|entry| entry.ok()).filter(|entry| entry.file_name() != ".DS_Store") {
+        summary.items += 1;
+        summary.bytes += stored_size(&entry.path());
+    }
+    Ok(summary)
+}
+
+#[cfg(target_os = "macos")]
+fn empty_system_trash() -> Result<TrashSummary, AppError> {
+    let summary = trash_summary()?;
+    
+    let output = std::process::Command::new("osascript")
+        .args(["-e", "tell application \"Finder\" to empty trash"])
+        .output()
+        .map_err(|e| AppError::io("Failed to ask Finder to empty the trash", e))?;
+    if !output.status.success() {
+        return Err(AppError::Io(format!(
+            "Failed to empty the trash: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(summary)
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+fn trash_summary() -> Result<TrashSummary, AppError> {
+    Err(AppError::Unsupported("Reading the trash is not supported on this platform".to_string()))
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+fn empty_system_trash() -> Result<TrashSummary, AppError> {
+    Err(AppError::Unsupported("Emptying the trash is not supported on this platform".to_string()))
+}
+
+// Bytes in a file, or in everything beneath a folder; links aren't followed
+#[cfg_attr(not(any(windows, target_os = "linux", target_os = "macos")), allow(dead_code))]
+fn stored_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    
+    fs::read_dir(path)
+        .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| stored_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::tags::TagStore;
+use crate::transfer::rename_or_copy;
+use crate::trash_bin::{restore_one, TrashLedger};
+use crate::AppError;
+
+// Oldest entries are dropped past this
+const MAX_UNDO_ENTRIES: usize = 50;
+
+// What `undo_last_operation` can reverse. Only moves and renames (including
+// `batch_rename`) and trashing are recorded. Never undoable: `delete_item`
+// (gone for good), copies and duplicates, created files and folders, text
+// writes, and moves that overwrote something, since the replaced item is lost.
+enum UndoAction {
+    // (from, to) for every item that ended up somewhere else
+    Moved(Vec<(PathBuf, PathBuf)>),
+    // Original path and trash id; items trashed without an id can't come back
+    Trashed(Vec<(PathBuf, Option<String>)>),
+}
+
+struct UndoEntry {
+    id: u64,
+    description: String,
+    timestamp: DateTime<Utc>,
+    action: UndoAction,
+}
+
+#[derive(Default)]
+pub struct UndoJournal(Mutex<UndoStack>);
+
+#[derive(Default)]
+struct UndoStack {
+    entries: VecDeque<UndoEntry>,
+    next_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoHistoryEntry {
+    id: u64,
+    description: String,
+    timestamp: DateTime<Utc>,
+    item_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoResult {
+    description: String,
+    restored: Vec<String>,
+    failed: Vec<String>, // one message per item that couldn't be put back
+}
+
+impl UndoJournal {
+    pub(crate) fn record_moves(&self, description: String, moves: Vec<(PathBuf, PathBuf)>) {
+        if !moves.is_empty() {
+            self.push(description, UndoAction::Moved(moves));
+        }
+    }
+    
+    pub(crate) fn record_trash(&self, description: String, items: Vec<(PathBuf, Option<String>)>) {
+        if !items.is_empty() {
+            self.push(description, UndoAction::Trashed(items));
+        }
+    }
+    
+    fn push(&self, description: String, action: UndoAction) {
+        let Ok(mut stack) = self.0.lock() else {
+            return;
+        };
+        
+        stack.next_id += 1;
+        let id = stack.next_id;
+        stack.entries.push_back(UndoEntry { id, description, timestamp: Utc::now(), action });
+        if stack.entries.len() > MAX_UNDO_ENTRIES {
+            stack.entries.pop_front();
+        }
+    }
+}
+
+// Newest first, i.e. in the order `undo_last_operation` would take them
+#[tauri::command]
+pub async fn get_undo_history(journal: tauri::State<'_, UndoJournal>) -> Result<Vec<UndoHistoryEntry>, AppError> {
+    let stack = journal.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock undo journal: {}", e)))?;
+    
+    Ok(stack.entries.iter()
+        .rev()
+        .map(|entry| UndoHistoryEntry {
+            id: entry.id,
+            description: entry.description.clone(),
+            timestamp: entry.timestamp,
+            item_count: match &entry.action {
+                UndoAction::Moved(moves) => moves.len(),
+                UndoAction::Trashed(items) => items.len(),
+            },
+        })
+        .collect())
+}
+
+// Reverses the most recent operation and removes it from the journal, even
+// if some items fail; those are listed in `failed`. Items are never put back
+// over something that has since taken their place.
+#[tauri::command]
+pub async fn undo_last_operation(
+    journal: tauri::State<'_, UndoJournal>,
+    ledger: tauri::State<'_, TrashLedger>,
+    tags: tauri::State<'_, TagStore>,
+) -> Result<UndoResult, AppError> {
+    let entry = journal.0.lock()
+        .map_err(|e| AppError::Internal(format!("Failed to lock undo journal: {}", e)))?
+        .entries
+        .pop_back()
+        .ok_or_else(|| AppError::NotFound("Nothing to undo".to_string()))?;
+    
+    let mut restored = Vec::new();
+    let mut failed = Vec::new();
+    
+    match entry.action {
+        UndoAction::Moved(moves) => {
+            // Backwards, so nested moves unwind in the right order
+            for (from, to) in moves.into_iter().rev() {
+                let name = from.to_string_lossy().to_string();
+                if from.exists() {
+                    failed.push(format!("{}: something already exists there", name));
+                    continue;
+                }
+                
+                let canonical = std::fs::canonicalize(&to).ok();
+                match rename_or_copy(&to, &from) {
+                    Ok(_) => {
+                        tags.move_path(canonical.as_deref(), &from);
+                        restored.push(name);
+                    }
+                    Err(e) => failed.push(format!("{}: {}", name, e)),
+                }
+            }
+        }
+        UndoAction::Trashed(items) => {
+            for (original, trash_id) in items {
+                let name = original.to_string_lossy().to_string();
+                let result = match trash_id {
+                    Some(trash_id) => restore_one(&trash_id, &ledger).map(|_| ()),
+                    None => Err(AppError::Unsupported("no trash id was available".to_string())),
+                };
+                match result {
+                    Ok(_) => restored.push(name),
+                    Err(e) => failed.push(format!("{}: {}", name, e)),
+                }
+            }
+        }
+    }
+    
+    Ok(UndoResult {
+        description: entry.description,
+        restored,
+        failed,
+    })
+}
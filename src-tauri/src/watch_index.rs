@@ -0,0 +1,147 @@
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::{file_item, FileItem};
+
+// What changed in a watched folder since the previous batch, worked out by
+// re-listing only the folders the batch touched. Items use their path as id.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexDelta {
+    added: Vec<FileItem>,
+    removed: Vec<FileItem>, // as last seen
+    modified: Vec<FileItem>,
+}
+
+impl IndexDelta {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+// Last known listing of every folder under a watch: just the root for a
+// non-recursive watch, the whole tree (symlinks not followed) otherwise.
+// Ignored paths are never indexed, so they never show up in a delta.
+pub(crate) struct DirectoryIndex {
+    root: PathBuf,
+    recursive: bool,
+    dirs: HashMap<PathBuf, HashMap<OsString, FileItem>>,
+}
+
+type IgnoreFn<'a> = &'a dyn Fn(&Path) -> bool;
+
+impl DirectoryIndex {
+    pub(crate) fn build(root: &Path, recursive: bool, ignored: IgnoreFn) -> Self {
+        let mut index = DirectoryIndex { root: root.to_path_buf(), recursive, dirs: HashMap::new() };
+        index.index_tree(root, ignored, &mut Vec::new());
+        index
+    }
+    
+    // Re-lists the folders containing `paths` (and any of `paths` that are
+    // themselves indexed folders), or every indexed folder on `rescan`
+    pub(crate) fn apply(&mut self, paths: &[PathBuf], rescan: bool, ignored: IgnoreFn) -> IndexDelta {
+        // Parents sort before their children, so a removed folder's subtree
+        // is dropped before it would be re-listed
+        let mut affected: BTreeSet<PathBuf> = BTreeSet::new();
+        if rescan {
+            affected.extend(self.dirs.keys().cloned());
+            affected.insert(self.root.clone());
+        }
+        for path in paths {
+            if let Some(parent) = path.parent().filter(|parent| self.dirs.contains_key(*parent)) {
+                affected.insert(parent.to_path_buf());
+            }
+            if self.dirs.contains_key(path) {
+                affected.insert(path.clone());
+            }
+        }
+        
+        let mut delta = IndexDelta::default();
+        for dir in affected {
+            if dir != self.root && !self.dirs.contains_key(&dir) {
+                continue;
+            }
+            self.relist(&dir, ignored, &mut delta);
+        }
+        delta
+    }
+    
+    fn relist(&mut self, dir: &Path, ignored: IgnoreFn, delta: &mut IndexDelta) {
+        let listing = read_level(dir, ignored);
+        let mut old = self.dirs.remove(dir).unwrap_or_default();
+        let new = listing.clone().unwrap_or_default();
+        
+        for (name, item) in &new {
+            match old.remove(name) {
+                None => {
+                    delta.added.push(item.clone());
+                    if self.descends_into(item) {
+                        self.index_tree(&dir.join(name), ignored, &mut delta.added);
+                    }
+                }
+                Some(previous) if changed(&previous, item) => delta.modified.push(item.clone()),
+                Some(_) => {}
+            }
+        }
+        
+        // Whatever is left in `old` is gone
+        for (name, item) in old {
+            let path = dir.join(&name);
+            self.dirs.retain(|indexed, _| !indexed.starts_with(&path));
+            delta.removed.push(item);
+        }
+        
+        if listing.is_some() || dir == self.root {
+            self.dirs.insert(dir.to_path_buf(), new);
+        }
+    }
+    
+    // Indexes `dir` and, for recursive watches, every folder below it. Each
+    // entry found below `dir` is also appended to `found`.
+    fn index_tree(&mut self, dir: &Path, ignored: IgnoreFn, found: &mut Vec<FileItem>) {
+        let Some(listing) = read_level(dir, ignored) else {
+            return;
+        };
+        
+        if dir != self.root {
+            found.extend(listing.values().cloned());
+        }
+        let subdirs: Vec<PathBuf> = listing.iter()
+            .filter(|(_, item)| self.descends_into(item))
+            .map(|(name, _)| dir.join(name))
+            .collect();
+        self.dirs.insert(dir.to_path_buf(), listing);
+        
+        for subdir in subdirs {
+            self.index_tree(&subdir, ignored, found);
+        }
+    }
+    
+    fn descends_into(&self, item: &FileItem) -> bool {
+        self.recursive && item.file_type == "folder" && item.symlink_target.is_none()
+    }
+}
+
+fn read_level(dir: &Path, ignored: IgnoreFn) -> Option<HashMap<OsString, FileItem>> {
+    let entries = fs::read_dir(dir).ok()?;
+    
+    let listing = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !ignored(&entry.path()))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let metadata = fs::symlink_metadata(&path).ok()?;
+            Some((entry.file_name(), file_item(&path, &metadata, path.to_string_lossy().to_string())))
+        })
+        .collect();
+    Some(listing)
+}
+
+fn changed(before: &FileItem, after: &FileItem) -> bool {
+    before.file_type != after.file_type
+        || before.size != after.size
+        || before.date_modified != after.date_modified
+        || before.symlink_target != after.symlink_target
+}
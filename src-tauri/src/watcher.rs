@@ -0,0 +1,900 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use serde::{Deserialize, Serialize};
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecursiveMode, Watcher};
+use notify_debouncer_full::{new_debouncer, Debouncer, FileIdMap};
+use tauri::{AppHandle, Emitter};
+
+use crate::watch_index::{DirectoryIndex, IndexDelta};
+use crate::{file_item, AppError, FileItem};
+
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+
+// Upper bound on how many `fs-change` events a single debounced batch may emit.
+// Bigger batches (e.g. `cargo clean` inside a recursively watched folder) are
+// collapsed into one "Rescan" event for the watch root, and the frontend
+// should re-list the directory instead of applying changes one by one.
+const MAX_EVENTS_PER_BATCH: usize = 256;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilesystemChange {
+    watch_id: String,
+    kind: ChangeKind,
+    paths: Vec<String>,
+}
+
+// Serializes as `{ type: "Created" }`, `{ type: "Renamed", from, to }`, ...
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed { from: String, to: String },
+    // The batch was too large to report; re-list the watch root
+    Rescan,
+    Other,
+}
+
+impl ChangeKind {
+    fn from_event(kind: &EventKind, paths: &[String]) -> Self {
+        match kind {
+            EventKind::Create(_) => ChangeKind::Created,
+            EventKind::Remove(_) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => ChangeKind::Renamed {
+                from: paths[0].clone(),
+                to: paths[1].clone(),
+            },
+            // Half of a rename whose other side is outside the watch
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => ChangeKind::Removed,
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => ChangeKind::Created,
+            EventKind::Modify(_) => ChangeKind::Modified,
+            EventKind::Access(_) | EventKind::Any | EventKind::Other => ChangeKind::Other,
+        }
+    }
+    
+    // Names accepted by `start_watch`'s `kinds` filter
+    const FILTER_NAMES: [&'static str; 5] = ["created", "modified", "removed", "renamed", "other"];
+    
+    fn filter_name(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "created",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Renamed { .. } => "renamed",
+            ChangeKind::Rescan => "rescan",
+            ChangeKind::Other => "other",
+        }
+    }
+}
+
+fn parse_kinds(kinds: Option<Vec<String>>) -> Result<Option<Vec<String>>, AppError> {
+    let Some(kinds) = kinds else {
+        return Ok(None);
+    };
+    
+    let mut parsed: Vec<String> = Vec::new();
+    for kind in kinds {
+        let kind = kind.to_lowercase();
+        if !ChangeKind::FILTER_NAMES.contains(&kind.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Unknown change kind '{}', expected one of: {}",
+                kind,
+                ChangeKind::FILTER_NAMES.join(", ")
+            )));
+        }
+        if !parsed.contains(&kind) {
+            parsed.push(kind);
+        }
+    }
+    Ok(Some(parsed))
+}
+
+// Filesystem watcher state, one debouncer per watched path. The path doubles
+// as the `watch_id` carried by every emitted event.
+type DebouncerType = Debouncer<notify::RecommendedWatcher, FileIdMap>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilesystemDelta {
+    watch_id: String,
+    #[serde(flatten)]
+    delta: IndexDelta,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigChanged {
+    watch_id: String,
+    content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigError {
+    watch_id: String,
+    error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewFileMatched {
+    watch_id: String,
+    item: FileItem,
+}
+
+// Stands in for a batch of `fs-change` events that would have gone over the
+// watch's `max_events_per_second`; re-list the watch root instead
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilesystemBurst {
+    watch_id: String,
+    change_count: usize, // how many events were held back
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchRootRemoved {
+    watch_id: String,
+}
+
+struct ActiveWatch {
+    // Never read, but dropping it is what stops the watch
+    _debouncer: DebouncerType,
+    recursive: bool,
+    debounce_ms: u64,
+    ignore_globs: Vec<String>,
+    coalesce: bool,
+    kinds: Option<Vec<String>>,
+    emit_deltas: bool,
+    max_events_per_second: Option<u32>,
+    config: bool, // a `watch_config` watch
+    inbox_pattern: Option<String>, // a `watch_for_new` watch
+    tracked_paths: Option<Vec<String>>, // a `watch_paths` watch
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+#[derive(Default)]
+pub struct WatcherState(Mutex<HashMap<String, ActiveWatch>>);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchStatus {
+    path: String,
+    recursive: bool,
+    debounce_ms: u64,
+    ignore_globs: Vec<String>,
+    coalesce: bool,
+    kinds: Option<Vec<String>>, // None reports every kind
+    emit_deltas: bool,
+    max_events_per_second: Option<u32>,
+    config: bool,
+    inbox_pattern: Option<String>,
+    tracked_paths: Option<Vec<String>>,
+    // False once the watched path is gone; notify stops reporting for it
+    // without any error of its own, so the UI should re-watch or navigate away
+    root_exists: bool,
+    last_error: Option<String>,
+}
+
+// Paths to leave out of a watch. A pattern without a "/" is tested against
+// every component of the path below the watch root, so "node_modules" drops
+// the whole folder and "*.tmp" any temp file. A pattern with a "/" is tested
+// against the relative path itself and each of its ancestors, so "src/gen"
+// drops that one folder and everything in it.
+struct IgnoreSet {
+    name_patterns: Vec<glob::Pattern>,
+    path_patterns: Vec<glob::Pattern>,
+}
+
+impl IgnoreSet {
+    fn new(globs: &[String]) -> Result<Self, AppError> {
+        let mut name_patterns = Vec::new();
+        let mut path_patterns = Vec::new();
+        for glob in globs {
+            let trimmed = glob.trim_matches('/');
+            let pattern = glob::Pattern::new(trimmed)
+                .map_err(|e| AppError::InvalidInput(format!("Invalid ignore glob '{}': {}", glob, e)))?;
+            if trimmed.contains('/') {
+                path_patterns.push(pattern);
+            } else {
+                name_patterns.push(pattern);
+            }
+        }
+        Ok(IgnoreSet { name_patterns, path_patterns })
+    }
+    
+    fn is_empty(&self) -> bool {
+        self.name_patterns.is_empty() && self.path_patterns.is_empty()
+    }
+    
+    fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        
+        let name_hit = relative.components()
+            .any(|part| self.name_patterns.iter().any(|p| p.matches(&part.as_os_str().to_string_lossy())));
+        if name_hit || self.path_patterns.is_empty() {
+            return name_hit;
+        }
+        
+        relative.ancestors()
+            .filter(|ancestor| !ancestor.as_os_str().is_empty())
+            .any(|ancestor| {
+                let ancestor = ancestor.to_string_lossy().replace('\\', "/");
+                self.path_patterns.iter().any(|p| p.matches(&ancestor))
+            })
+    }
+}
+
+// `kinds` limits the reported changes to the listed kinds ("created",
+// "modified", "removed", "renamed", "other"). A "Rescan" is always reported.
+//
+// `max_events_per_second` caps the `fs-change` stream: a batch that would go
+// over it within the current second is replaced by one `fs-change-burst`
+// event, and the frontend should re-list rather than wait for the details.
+// Batches come at most once per debounce interval, so bursts stay rare too.
+//
+// With `emit_deltas` the watch also keeps an index of the folder (the whole
+// tree when recursive) and after each batch emits an `fs-delta` event with the
+// added, removed and modified items, so the frontend can patch its listing
+// instead of re-listing. Not available when watching a single file.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn start_watch(
+    path: String,
+    recursive: Option<bool>,
+    debounce_ms: Option<u64>,
+    ignore_globs: Option<Vec<String>>,
+    coalesce: Option<bool>,
+    kinds: Option<Vec<String>>,
+    emit_deltas: Option<bool>,
+    max_events_per_second: Option<u32>,
+    app_handle: AppHandle,
+    state: tauri::State<'_, WatcherState>,
+) -> Result<(), AppError> {
+    if max_events_per_second == Some(0) {
+        return Err(AppError::InvalidInput("max_events_per_second must be at least 1".to_string()));
+    }
+    let mut rate = max_events_per_second.map(EventRate::new);
+    let ignore_globs = ignore_globs.unwrap_or_default();
+    let ignore = Arc::new(IgnoreSet::new(&ignore_globs)?);
+    let coalesce = coalesce.unwrap_or(false);
+    let kinds = parse_kinds(kinds)?;
+    let callback_kinds = kinds.clone();
+    
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    
+    // Restarting a watch on the same path replaces it; other watches are untouched
+    watchers.remove(&path);
+    
+    // A file is watched through its parent folder, keeping only its own events.
+    // Editors that save by renaming a temp file over it still show up, as a
+    // rename whose destination is the file.
+    let target = Path::new(&path);
+    let (watch_path, watched_file) = match (target.is_file(), target.parent(), target.file_name()) {
+        (true, Some(parent), Some(name)) if parent.as_os_str().is_empty() => (Path::new("."), Some(name.to_os_string())),
+        (true, Some(parent), Some(name)) => (parent, Some(name.to_os_string())),
+        _ => (target, None),
+    };
+    let is_file_watch = watched_file.is_some();
+    let recursive = recursive.unwrap_or(false) && !is_file_watch;
+    
+    let emit_deltas = emit_deltas.unwrap_or(false) && !is_file_watch;
+    let index: Arc<Mutex<Option<DirectoryIndex>>> = Arc::new(Mutex::new(None));
+    let callback_index = index.clone();
+    let index_ignore = ignore.clone();
+    
+    // Create new debounced watcher
+    let app_handle_clone = app_handle.clone();
+    let watch_id = path.clone();
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+    let last_error = Arc::new(Mutex::new(None));
+    let callback_error = last_error.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            // Checked ahead of the batch cap so it's never folded into a "Rescan"
+            if let Ok(events) = &result {
+                if events.iter().any(|event| removes_root(event, &watch_id)) {
+                    let _ = app_handle_clone.emit("watch-root-removed", WatchRootRemoved {
+                        watch_id: watch_id.clone(),
+                    });
+                }
+            }
+            
+            // Ignored churn is dropped before the batch cap, so it can't force a rescan
+            let result = result.map(|mut events| {
+                if let Some(file_name) = &watched_file {
+                    events.retain(|event| event.paths.iter().any(|path| path.file_name() == Some(file_name.as_os_str())));
+                }
+                if !ignore.is_empty() {
+                    let root = Path::new(&watch_id);
+                    events.retain(|event| !event.paths.iter().all(|path| ignore.is_ignored(root, path)));
+                }
+                events
+            });
+            
+            if let (Ok(events), Ok(mut index)) = (&result, callback_index.lock()) {
+                if let Some(index) = index.as_mut() {
+                    let root = Path::new(&watch_id);
+                    let rescan = events.iter().any(|event| event.need_rescan());
+                    let paths: Vec<_> = events.iter().flat_map(|event| event.paths.iter().cloned()).collect();
+                    let delta = index.apply(&paths, rescan, &|path: &Path| ignore.is_ignored(root, path));
+                    if !delta.is_empty() {
+                        let _ = app_handle_clone.emit("fs-delta", FilesystemDelta {
+                            watch_id: watch_id.clone(),
+                            delta,
+                        });
+                    }
+                }
+            }
+            
+            let result = result.map(|events| {
+                let changes: Vec<(ChangeKind, Vec<String>)> = events.iter()
+                    .map(|event| {
+                        let paths: Vec<String> = event.paths.iter().map(|p| p.display().to_string()).collect();
+                        (ChangeKind::from_event(&event.event.kind, &paths), paths)
+                    })
+                    .collect();
+                let mut changes = if coalesce { coalesce_changes(changes) } else { changes };
+                // Filtered after coalescing, which needs the full picture, but
+                // ahead of the batch cap so unwanted kinds can't force a rescan
+                if let Some(kinds) = &callback_kinds {
+                    changes.retain(|(kind, _)| {
+                        *kind == ChangeKind::Rescan || kinds.iter().any(|name| name == kind.filter_name())
+                    });
+                }
+                changes
+            });
+            
+            let result = result.map(|changes| {
+                if rate.as_mut().is_some_and(|rate| !rate.admit(changes.len())) {
+                    let _ = app_handle_clone.emit("fs-change-burst", FilesystemBurst {
+                        watch_id: watch_id.clone(),
+                        change_count: changes.len(),
+                    });
+                    return Vec::new();
+                }
+                changes
+            });
+            
+            match result {
+                Ok(changes) if changes.len() > MAX_EVENTS_PER_BATCH => {
+                    let fs_change = FilesystemChange {
+                        watch_id: watch_id.clone(),
+                        kind: ChangeKind::Rescan,
+                        paths: vec![watch_id.clone()],
+                    };
+                    let _ = app_handle_clone.emit("fs-change", fs_change);
+                }
+                Ok(changes) => {
+                    for (kind, paths) in changes {
+                        let fs_change = FilesystemChange {
+                            watch_id: watch_id.clone(),
+                            kind,
+                            paths,
+                        };
+                        let _ = app_handle_clone.emit("fs-change", fs_change);
+                    }
+                }
+                Err(errors) => {
+                    for error in &errors {
+                        eprintln!("Filesystem watch error: {:?}", error);
+                    }
+                    if let (Some(error), Ok(mut last_error)) = (errors.last(), callback_error.lock()) {
+                        *last_error = Some(error.to_string());
+                    }
+                }
+            }
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    
+    // Watch the directory
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    debouncer.watcher().watch(watch_path, mode)
+        .map_err(|e| AppError::Io(format!("Failed to watch directory: {}", e)))?;
+    
+    // Built once the watch is running so nothing slips between the two. A
+    // batch arriving before it's in place gets no delta, and the listing the
+    // index starts from already includes its changes.
+    if emit_deltas {
+        let root = Path::new(&path);
+        let built = DirectoryIndex::build(root, recursive, &|entry: &Path| index_ignore.is_ignored(root, entry));
+        if let Ok(mut index) = index.lock() {
+            *index = Some(built);
+        }
+    }
+    
+    watchers.insert(path, ActiveWatch {
+        _debouncer: debouncer,
+        recursive,
+        debounce_ms,
+        ignore_globs,
+        coalesce,
+        kinds,
+        emit_deltas,
+        max_events_per_second,
+        config: false,
+        inbox_pattern: None,
+        tracked_paths: None,
+        last_error,
+    });
+    
+    Ok(())
+}
+
+// `fs-change` events emitted in the current one-second window
+struct EventRate {
+    limit: u32,
+    window_start: Instant,
+    emitted: usize,
+}
+
+impl EventRate {
+    fn new(limit: u32) -> Self {
+        EventRate { limit, window_start: Instant::now(), emitted: 0 }
+    }
+    
+    // Whether a batch of `count` events still fits in this second, counting
+    // it if so. A batch over the cap only ever counts as its one "Rescan".
+    fn admit(&mut self, count: usize) -> bool {
+        if self.window_start.elapsed() >= Duration::from_secs(1) {
+            self.window_start = Instant::now();
+            self.emitted = 0;
+        }
+        
+        let count = if count > MAX_EVENTS_PER_BATCH { 1 } else { count };
+        if self.emitted + count > self.limit as usize {
+            return false;
+        }
+        self.emitted += count;
+        true
+    }
+}
+
+// Collapses each path's changes within one debounced batch into the net
+// effect: present before and after is `Modified`, appearing is `Created`,
+// disappearing is `Removed`, and a path that came and went (an editor's
+// temp or backup file) is dropped. A Vim-style save (rename away, create,
+// write, delete backup) thus becomes a single `Modified`. Renames stay
+// renames unless another change in the batch touches one of their paths.
+fn coalesce_changes(changes: Vec<(ChangeKind, Vec<String>)>) -> Vec<(ChangeKind, Vec<String>)> {
+    let mut touches: HashMap<&str, usize> = HashMap::new();
+    for (_, paths) in &changes {
+        for path in paths {
+            *touches.entry(path.as_str()).or_default() += 1;
+        }
+    }
+    
+    // Per path, in order of first appearance: (existed before, exists after)
+    let mut order: Vec<String> = Vec::new();
+    let mut states: HashMap<String, (bool, bool)> = HashMap::new();
+    let mut passthrough = Vec::new();
+    let mut record = |path: &str, before: bool, after: bool| {
+        match states.get_mut(path) {
+            Some(state) => state.1 = after,
+            None => {
+                order.push(path.to_string());
+                states.insert(path.to_string(), (before, after));
+            }
+        }
+    };
+    
+    for (kind, paths) in &changes {
+        match kind {
+            ChangeKind::Renamed { from, to } if touches[from.as_str()] == 1 && touches[to.as_str()] == 1 => {
+                passthrough.push((kind.clone(), paths.clone()));
+            }
+            ChangeKind::Renamed { from, to } => {
+                record(from, true, false);
+                record(to, false, true);
+            }
+            ChangeKind::Created => paths.iter().for_each(|path| record(path, false, true)),
+            ChangeKind::Removed => paths.iter().for_each(|path| record(path, true, false)),
+            ChangeKind::Modified => paths.iter().for_each(|path| record(path, true, true)),
+            ChangeKind::Rescan | ChangeKind::Other => passthrough.push((kind.clone(), paths.clone())),
+        }
+    }
+    
+    let mut coalesced: Vec<(ChangeKind, Vec<String>)> = order.into_iter()
+        .filter_map(|path| {
+            let kind = match states[&path] {
+                (true, true) => ChangeKind::Modified,
+                (false, true) => ChangeKind::Created,
+                (true, false) => ChangeKind::Removed,
+                (false, false) => return None,
+            };
+            Some((kind, vec![path]))
+        })
+        .collect();
+    coalesced.extend(passthrough);
+    coalesced
+}
+
+// Deleting the watched folder, or moving it away, both end the watch
+fn removes_root(event: &notify_debouncer_full::DebouncedEvent, root: &str) -> bool {
+    let gone = matches!(
+        event.event.kind,
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From))
+    );
+    let root_moved = matches!(event.event.kind, EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+        && event.paths.first().is_some_and(|path| path == Path::new(root));
+    
+    root_moved || (gone && event.paths.iter().any(|path| path == Path::new(root)))
+}
+
+// Watches one text file and, after each debounced burst of changes, re-reads
+// it and emits `config-changed` with the new content, or `config-error` when
+// it can't be read (removed, not UTF-8, ...). Returns the current content, so
+// nothing is missed between the first read and the watch starting. Shares
+// the watch table with `start_watch`: `stop_watch(path)` ends it.
+#[tauri::command]
+pub async fn watch_config(
+    path: String,
+    debounce_ms: Option<u64>,
+    app_handle: AppHandle,
+    state: tauri::State<'_, WatcherState>,
+) -> Result<String, AppError> {
+    let target = Path::new(&path);
+    if !target.is_file() {
+        return Err(AppError::NotFound("Config file does not exist".to_string()));
+    }
+    let (parent, file_name) = match (target.parent(), target.file_name()) {
+        (Some(parent), Some(name)) if parent.as_os_str().is_empty() => (Path::new("."), name.to_os_string()),
+        (Some(parent), Some(name)) => (parent, name.to_os_string()),
+        _ => return Err(AppError::InvalidInput("Config path has no file name".to_string())),
+    };
+    
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    watchers.remove(&path);
+    
+    let watch_id = path.clone();
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+    let last_error = Arc::new(Mutex::new(None));
+    let callback_error = last_error.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            let outcome = match result {
+                // The whole burst collapses into one read of the final content
+                Ok(events) if !events.iter().any(|event| {
+                    event.paths.iter().any(|path| path.file_name() == Some(file_name.as_os_str()))
+                }) => return,
+                Ok(_) => read_config(Path::new(&watch_id)),
+                Err(errors) => Err(errors.last().map(|e| e.to_string()).unwrap_or_default()),
+            };
+            
+            match outcome {
+                Ok(content) => {
+                    let _ = app_handle.emit("config-changed", ConfigChanged { watch_id: watch_id.clone(), content });
+                }
+                Err(error) => {
+                    if let Ok(mut last_error) = callback_error.lock() {
+                        *last_error = Some(error.clone());
+                    }
+                    let _ = app_handle.emit("config-error", ConfigError { watch_id: watch_id.clone(), error });
+                }
+            }
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    
+    debouncer.watcher().watch(parent, RecursiveMode::NonRecursive)
+        .map_err(|e| AppError::Io(format!("Failed to watch directory: {}", e)))?;
+    
+    // Read only once watching, so a change in between is reported as well
+    let content = read_config(target).map_err(AppError::Io)?;
+    
+    watchers.insert(path, ActiveWatch {
+        _debouncer: debouncer,
+        recursive: false,
+        debounce_ms,
+        ignore_globs: Vec::new(),
+        coalesce: true,
+        kinds: None,
+        emit_deltas: false,
+        max_events_per_second: None,
+        config: true,
+        inbox_pattern: None,
+        tracked_paths: None,
+        last_error,
+    });
+    
+    Ok(content)
+}
+
+fn read_config(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read config file: {}", e))?;
+    String::from_utf8(bytes).map_err(|_| "Config file is not valid UTF-8".to_string())
+}
+
+// Watches a folder, not recursively, for new files whose name matches
+// `pattern` (a glob, case-insensitive) and emits `new-file-matched` for each
+// once it's complete: its size and mtime have to hold still across a whole
+// debounce window, so a download in progress isn't reported half written. A
+// file renamed into the folder, or renamed to a matching name (as browsers do
+// when a download finishes), counts as new. Files already there don't.
+// `stop_watch(path)` ends it.
+#[tauri::command]
+pub async fn watch_for_new(
+    path: String,
+    pattern: String,
+    debounce_ms: Option<u64>,
+    app_handle: AppHandle,
+    state: tauri::State<'_, WatcherState>,
+) -> Result<(), AppError> {
+    if !Path::new(&path).is_dir() {
+        return Err(AppError::NotADirectory(format!("{} is not a directory", path)));
+    }
+    let glob = glob::Pattern::new(&pattern)
+        .map_err(|e| AppError::InvalidInput(format!("Invalid glob pattern: {}", e)))?;
+    let options = glob::MatchOptions { case_sensitive: false, ..Default::default() };
+    
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    watchers.remove(&path);
+    
+    // New files waiting to settle, with the size and mtime last seen. Only
+    // the debouncer's callback holds it, so the checker below stops once the
+    // watch is dropped.
+    let pending: Arc<Mutex<HashMap<PathBuf, FileSample>>> = Arc::new(Mutex::new(HashMap::new()));
+    let callback_pending = pending.clone();
+    
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+    let last_error = Arc::new(Mutex::new(None));
+    let callback_error = last_error.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            let events = match result {
+                Ok(events) => events,
+                Err(errors) => {
+                    if let (Some(error), Ok(mut last_error)) = (errors.last(), callback_error.lock()) {
+                        *last_error = Some(error.to_string());
+                    }
+                    return;
+                }
+            };
+            let Ok(mut pending) = callback_pending.lock() else {
+                return;
+            };
+            
+            for event in events {
+                let arrived = match event.event.kind {
+                    EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => event.paths.first(),
+                    EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => event.paths.get(1),
+                    _ => None,
+                };
+                let Some(arrived) = arrived.filter(|path| {
+                    path.file_name().is_some_and(|name| glob.matches_with(&name.to_string_lossy(), options))
+                }) else {
+                    continue;
+                };
+                if let Some(sample) = sample_file(arrived) {
+                    pending.entry(arrived.clone()).or_insert(sample);
+                }
+            }
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    
+    debouncer.watcher().watch(Path::new(&path), RecursiveMode::NonRecursive)
+        .map_err(|e| AppError::Io(format!("Failed to watch directory: {}", e)))?;
+    
+    let watch_id = path.clone();
+    let pending = Arc::downgrade(&pending);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(debounce_ms.max(50)));
+        let Some(pending) = pending.upgrade() else {
+            break;
+        };
+        let Ok(mut pending) = pending.lock() else {
+            break;
+        };
+        
+        // Gone before it settled means it was a temp file; drop it quietly
+        pending.retain(|file, last| match sample_file(file) {
+            Some(sample) if sample != *last => {
+                *last = sample;
+                true
+            }
+            Some(_) => {
+                if let Ok(metadata) = fs::symlink_metadata(file) {
+                    let item = file_item(file, &metadata, file.to_string_lossy().to_string());
+                    let _ = app_handle.emit("new-file-matched", NewFileMatched { watch_id: watch_id.clone(), item });
+                }
+                false
+            }
+            None => false,
+        });
+    });
+    
+    watchers.insert(path, ActiveWatch {
+        _debouncer: debouncer,
+        recursive: false,
+        debounce_ms,
+        ignore_globs: Vec::new(),
+        coalesce: false,
+        kinds: None,
+        emit_deltas: false,
+        max_events_per_second: None,
+        config: false,
+        inbox_pattern: Some(pattern),
+        tracked_paths: None,
+        last_error,
+    });
+    
+    Ok(())
+}
+
+// Watches exactly the given files, wherever they are: each folder holding one
+// is watched once, not recursively, and only events for the files themselves
+// are emitted as `fs-change`, with the paths as they were passed in. Files
+// that don't exist yet are reported when they appear. A rename between a
+// tracked file and an untracked name is reported as the tracked side being
+// removed or created. Returns the `watch_id` ("paths-1", ...) carried by the
+// events, which `stop_watch` takes to end it.
+#[tauri::command]
+pub async fn watch_paths(
+    paths: Vec<String>,
+    debounce_ms: Option<u64>,
+    app_handle: AppHandle,
+    state: tauri::State<'_, WatcherState>,
+) -> Result<String, AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput("No paths to watch".to_string()));
+    }
+    
+    // Requested paths by folder and name. Folders are canonical, as some
+    // platforms report events under the resolved path.
+    let mut tracked: HashMap<PathBuf, HashMap<std::ffi::OsString, String>> = HashMap::new();
+    for path in &paths {
+        let absolute = std::path::absolute(path).map_err(|e| AppError::io("Failed to resolve path", e))?;
+        let (Some(parent), Some(name)) = (absolute.parent(), absolute.file_name()) else {
+            return Err(AppError::InvalidInput(format!("{} has no file name", path)));
+        };
+        let parent = fs::canonicalize(parent)
+            .map_err(|_| AppError::NotFound(format!("The folder holding {} does not exist", path)))?;
+        tracked.entry(parent).or_default().insert(name.to_os_string(), path.clone());
+    }
+    let folders: Vec<PathBuf> = tracked.keys().cloned().collect();
+    
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    let watch_id = (1..)
+        .map(|n| format!("paths-{}", n))
+        .find(|id| !watchers.contains_key(id))
+        .unwrap_or_default();
+    
+    let callback_id = watch_id.clone();
+    let all_paths = paths.clone();
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+    let last_error = Arc::new(Mutex::new(None));
+    let callback_error = last_error.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(debounce_ms),
+        None,
+        move |result: Result<Vec<notify_debouncer_full::DebouncedEvent>, Vec<notify::Error>>| {
+            let events = match result {
+                Ok(events) => events,
+                Err(errors) => {
+                    if let (Some(error), Ok(mut last_error)) = (errors.last(), callback_error.lock()) {
+                        *last_error = Some(error.to_string());
+                    }
+                    return;
+                }
+            };
+            let requested = |path: &Path| -> Option<String> {
+                let (parent, name) = (path.parent()?, path.file_name()?);
+                let by_name = tracked.get(parent)
+                    .or_else(|| fs::canonicalize(parent).ok().and_then(|parent| tracked.get(&parent)))?;
+                by_name.get(name).cloned()
+            };
+            
+            let mut changes = Vec::new();
+            for event in &events {
+                if event.need_rescan() {
+                    changes.push((ChangeKind::Rescan, all_paths.clone()));
+                    continue;
+                }
+                let matched: Vec<Option<String>> = event.paths.iter().map(|path| requested(path)).collect();
+                let change = match (&event.event.kind, matched.as_slice()) {
+                    (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) => match (from, to) {
+                        (Some(from), Some(to)) => Some((
+                            ChangeKind::Renamed { from: from.clone(), to: to.clone() },
+                            vec![from.clone(), to.clone()],
+                        )),
+                        (Some(from), None) => Some((ChangeKind::Removed, vec![from.clone()])),
+                        (None, Some(to)) => Some((ChangeKind::Created, vec![to.clone()])),
+                        (None, None) => None,
+                    },
+                    (kind, _) => {
+                        let paths: Vec<String> = matched.into_iter().flatten().collect();
+                        (!paths.is_empty()).then(|| (ChangeKind::from_event(kind, &paths), paths))
+                    }
+                };
+                changes.extend(change);
+            }
+            
+            for (kind, paths) in coalesce_changes(changes) {
+                let _ = app_handle.emit("fs-change", FilesystemChange { watch_id: callback_id.clone(), kind, paths });
+            }
+        },
+    ).map_err(|e| AppError::Io(format!("Failed to create watcher: {}", e)))?;
+    
+    for folder in &folders {
+        debouncer.watcher().watch(folder, RecursiveMode::NonRecursive)
+            .map_err(|e| AppError::Io(format!("Failed to watch {}: {}", folder.display(), e)))?;
+    }
+    
+    watchers.insert(watch_id.clone(), ActiveWatch {
+        _debouncer: debouncer,
+        recursive: false,
+        debounce_ms,
+        ignore_globs: Vec::new(),
+        coalesce: true,
+        kinds: None,
+        emit_deltas: false,
+        max_events_per_second: None,
+        config: false,
+        inbox_pattern: None,
+        tracked_paths: Some(paths),
+        last_error,
+    });
+    
+    Ok(watch_id)
+}
+
+type FileSample = (u64, Option<SystemTime>);
+
+// None for anything that isn't (or is no longer) a regular file
+fn sample_file(path: &Path) -> Option<FileSample> {
+    let metadata = fs::metadata(path).ok().filter(|metadata| metadata.is_file())?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
+
+#[tauri::command]
+pub async fn stop_watch(path: String, state: tauri::State<'_, WatcherState>) -> Result<(), AppError> {
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    watchers.remove(&path);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_all_watches(state: tauri::State<'_, WatcherState>) -> Result<(), AppError> {
+    let mut watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    watchers.clear();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_watch_status(state: tauri::State<'_, WatcherState>) -> Result<Vec<WatchStatus>, AppError> {
+    let watchers = state.0.lock().map_err(|e| AppError::Internal(format!("Failed to lock watcher: {}", e)))?;
+    
+    let mut statuses: Vec<WatchStatus> = watchers.iter()
+        .map(|(path, watch)| WatchStatus {
+            path: path.clone(),
+            recursive: watch.recursive,
+            debounce_ms: watch.debounce_ms,
+            ignore_globs: watch.ignore_globs.clone(),
+            coalesce: watch.coalesce,
+            kinds: watch.kinds.clone(),
+            emit_deltas: watch.emit_deltas,
+            max_events_per_second: watch.max_events_per_second,
+            config: watch.config,
+            inbox_pattern: watch.inbox_pattern.clone(),
+            tracked_paths: watch.tracked_paths.clone(),
+            // For a set of files, whether every folder holding one is still there
+            root_exists: match &watch.tracked_paths {
+                Some(paths) => paths.iter().all(|path| {
+                    std::path::absolute(path).is_ok_and(|path| path.parent().is_some_and(Path::exists))
+                }),
+                None => Path::new(path).exists(),
+            },
+            last_error: watch.last_error.lock().ok().and_then(|error| error.clone()),
+        })
+        .collect();
+    statuses.sort_by(|a, b| a.path.cmp(&b.path));
+    
+    Ok(statuses)
+}
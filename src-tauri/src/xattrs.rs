@@ -0,0 +1,168 @@
+use std::path::Path;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct XattrValue {
+    name: String,
+    value: String,        // base64, since attributes are arbitrary bytes
+    text: Option<String>, // the value again when it's valid UTF-8
+}
+
+// A Finder tag. Colors are Finder's indexes: 0 none, 1 gray, 2 green,
+// 3 purple, 4 blue, 5 yellow, 6 red, 7 orange.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FinderTag {
+    name: String,
+    color: Option<u8>,
+}
+
+fn existing(path: &str) -> Result<&Path, AppError> {
+    let item_path = Path::new(path);
+    if !item_path.exists() {
+        return Err(AppError::NotFound("Item does not exist".to_string()));
+    }
+    Ok(item_path)
+}
+
+// Extended attributes exist on macOS, Linux and the BSDs. On Linux, user
+// attributes need the "user." prefix.
+#[tauri::command]
+pub async fn list_xattrs(path: String) -> Result<Vec<String>, AppError> {
+    let mut names = read_names(existing(&path)?)?;
+    names.sort();
+    Ok(names)
+}
+
+#[tauri::command]
+pub async fn get_xattr(path: String, name: String) -> Result<XattrValue, AppError> {
+    let value = read_attr(existing(&path)?, &name)?
+        .ok_or_else(|| AppError::NotFound(format!("No attribute named '{}'", name)))?;
+    
+    Ok(XattrValue {
+        text: String::from_utf8(value.clone()).ok(),
+        value: STANDARD.encode(&value),
+        name,
+    })
+}
+
+// `value` is taken as text unless `is_base64` is set
+#[tauri::command]
+pub async fn set_xattr(
+    path: String,
+    name: String,
+    value: String,
+    is_base64: Option<bool>,
+) -> Result<(), AppError> {
+    let bytes = if is_base64.unwrap_or(false) {
+        STANDARD.decode(value.as_bytes())
+            .map_err(|e| AppError::InvalidInput(format!("Invalid base64 value: {}", e)))?
+    } else {
+        value.into_bytes()
+    };
+    
+    write_attr(existing(&path)?, &name, &bytes)
+}
+
+// Finder keeps tags in a binary plist of "name\ncolor" strings. Only the
+// attribute is written; Finder picks the change up on its next refresh.
+#[tauri::command]
+pub async fn get_finder_tags(path: String) -> Result<Vec<FinderTag>, AppError> {
+    read_finder_tags(existing(&path)?)
+}
+
+#[tauri::command]
+pub async fn set_finder_tags(path: String, tags: Vec<FinderTag>) -> Result<Vec<FinderTag>, AppError> {
+    let item_path = existing(&path)?;
+    
+    if let Some(tag) = tags.iter().find(|tag| tag.color.is_some_and(|color| color > 7)) {
+        return Err(AppError::InvalidInput(format!("Tag '{}' has an invalid color, expected 0-7", tag.name)));
+    }
+    if tags.iter().any(|tag| tag.name.trim().is_empty() || tag.name.contains('\n')) {
+        return Err(AppError::InvalidInput("Tag names must be non-empty single lines".to_string()));
+    }
+    
+    write_finder_tags(item_path, &tags)?;
+    read_finder_tags(item_path)
+}
+
+#[cfg(unix)]
+fn read_names(path: &Path) -> Result<Vec<String>, AppError> {
+    let names = xattr::list(path).map_err(|e| AppError::io("Failed to list attributes", e))?;
+    Ok(names.map(|name| name.to_string_lossy().to_string()).collect())
+}
+
+#[cfg(unix)]
+fn read_attr(path: &Path, name: &str) -> Result<Option<Vec<u8>>, AppError> {
+    xattr::get(path, name).map_err(|e| AppError::io("Failed to read attribute", e))
+}
+
+#[cfg(unix)]
+fn write_attr(path: &Path, name: &str, value: &[u8]) -> Result<(), AppError> {
+    xattr::set(path, name, value).map_err(|e| AppError::io("Failed to write attribute", e))
+}
+
+#[cfg(not(unix))]
+fn read_names(_path: &Path) -> Result<Vec<String>, AppError> {
+    Err(AppError::Unsupported("Extended attributes are not supported on this platform".to_string()))
+}
+
+#[cfg(not(unix))]
+fn read_attr(_path: &Path, _name: &str) -> Result<Option<Vec<u8>>, AppError> {
+    Err(AppError::Unsupported("Extended attributes are not supported on this platform".to_string()))
+}
+
+#[cfg(not(unix))]
+fn write_attr(_path: &Path, _name: &str, _value: &[u8]) -> Result<(), AppError> {
+    Err(AppError::Unsupported("Extended attributes are not supported on this platform".to_string()))
+}
+
+#[cfg(target_os = "macos")]
+const FINDER_TAGS: &str = "com.apple.metadata:_kMDItemUserTags";
+
+#[cfg(target_os = "macos")]
+fn read_finder_tags(path: &Path) -> Result<Vec<FinderTag>, AppError> {
+    let Some(bytes) = read_attr(path, FINDER_TAGS)? else {
+        return Ok(Vec::new());
+    };
+    let entries: Vec<String> = plist::from_bytes(&bytes)
+        .map_err(|e| AppError::Io(format!("Unreadable Finder tags: {}", e)))?;
+    
+    Ok(entries.into_iter()
+        .map(|entry| match entry.rsplit_once('\n') {
+            Some((name, color)) => FinderTag { name: name.to_string(), color: color.parse().ok() },
+            None => FinderTag { name: entry, color: None },
+        })
+        .collect())
+}
+
+#[cfg(target_os = "macos")]
+fn write_finder_tags(path: &Path, tags: &[FinderTag]) -> Result<(), AppError> {
+    if tags.is_empty() {
+        return match xattr::remove(path, FINDER_TAGS) {
+            Ok(()) => Ok(()),
+            Err(_) if read_attr(path, FINDER_TAGS)?.is_none() => Ok(()),
+            Err(e) => Err(AppError::io("Failed to clear Finder tags", e)),
+        };
+    }
+    
+    let entries: Vec<String> = tags.iter()
+        .map(|tag| format!("{}\n{}", tag.name.trim(), tag.color.unwrap_or(0)))
+        .collect();
+    let mut bytes = Vec::new();
+    plist::to_writer_binary(&mut bytes, &entries)
+        .map_err(|e| AppError::Internal(format!("Failed to encode Finder tags: {}", e)))?;
+    write_attr(path, FINDER_TAGS, &bytes)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_finder_tags(_path: &Path) -> Result<Vec<FinderTag>, AppError> {
+    Err(AppError::Unsupported("Finder tags are only available on macOS".to_string()))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn write_finder_tags(_path: &Path, _tags: &[FinderTag]) -> Result<(), AppError> {
+    Err(AppError::Unsupported("Finder tags are only available on macOS".to_string()))
+}
@@ -29,6 +29,7 @@ import { Label } from '../ui/label';
 import { invoke } from '@tauri-apps/api/core';
 import { useState } from 'react';
 import { toast } from 'sonner';
+import { errorMessage } from '@/lib/utils';
 import { useClipboardStore } from '@/stores/clipboardStore';
 
 interface CommandsPalletProps {
@@ -74,7 +75,7 @@ export default function CommandsPallet({
       setFolderDialogOpen(false);
       onRefresh();
     } catch (error) {
-      toast.error(`Failed to create folder: ${error}`);
+      toast.error(`Failed to create folder: ${errorMessage(error)}`);
     } finally {
       setIsLoading(false);
     }
@@ -97,7 +98,7 @@ export default function CommandsPallet({
       setFileDialogOpen(false);
       onRefresh();
     } catch (error) {
-      toast.error(`Failed to create file: ${error}`);
+      toast.error(`Failed to create file: ${errorMessage(error)}`);
     } finally {
       setIsLoading(false);
     }
@@ -120,7 +121,7 @@ export default function CommandsPallet({
       onItemsDeleted();
       onRefresh();
     } catch (error) {
-      toast.error(`Failed to delete items: ${error}`);
+      toast.error(`Failed to delete items: ${errorMessage(error)}`);
     } finally {
       setIsLoading(false);
     }
@@ -184,7 +185,7 @@ export default function CommandsPallet({
 
       onRefresh();
     } catch (error) {
-      toast.error(`Failed to paste: ${error}`);
+      toast.error(`Failed to paste: ${errorMessage(error)}`);
     } finally {
       setIsLoading(false);
     }
@@ -47,6 +47,7 @@ import {
 } from '@/components/ui/context-menu';
 import { PreviewPane } from './previewPane';
 import { toast } from 'sonner';
+import { errorMessage } from '@/lib/utils';
 import TitleBar from './titleBar';
 import { NavigationBar } from './navigation';
 import { Toolbar } from './Toolbar';
@@ -192,7 +193,13 @@ export function FileStructure() {
         await invoke('start_watch', { path: currentPath });
 
         // Listen for filesystem change events
-        unlisten = await listen<{ kind: string; paths: string[] }>(
+        unlisten = await listen<{
+          watch_id: string;
+          kind:
+            | { type: 'Created' | 'Modified' | 'Removed' | 'Rescan' | 'Other' }
+            | { type: 'Renamed'; from: string; to: string };
+          paths: string[];
+        }>(
           'fs-change',
           (event) => {
             const payload = event.payload;
@@ -203,26 +210,17 @@ export function FileStructure() {
             }
 
             // Convert Rust event to TQL FSEvent format
-            // Rust emits: { kind: "Create(...)", paths: ["/path/to/file"] }
-            const kind = payload.kind.toLowerCase();
+            // Rust emits: { kind: { type: "Created" }, paths: ["/path/to/file"] }
             const paths = payload.paths;
-
-            // Parse event kind (Rust uses Debug format like "Create(File)")
-            let eventKind:
-              | 'create'
-              | 'modify'
-              | 'remove'
-              | 'rename'
-              | 'unknown' = 'unknown';
-            if (kind.includes('create')) {
-              eventKind = 'create';
-            } else if (kind.includes('modify') || kind.includes('write')) {
-              eventKind = 'modify';
-            } else if (kind.includes('remove') || kind.includes('delete')) {
-              eventKind = 'remove';
-            } else if (kind.includes('rename')) {
-              eventKind = 'rename';
-            }
+            const kindMap = {
+              Created: 'create',
+              Modified: 'modify',
+              Removed: 'remove',
+              Renamed: 'rename',
+              Rescan: 'unknown',
+              Other: 'unknown',
+            } as const;
+            const eventKind = kindMap[payload.kind.type] ?? 'unknown';
 
             if (import.meta.env.DEV) {
               console.log('[FS Event Parsed]', { eventKind, paths });
@@ -259,7 +257,7 @@ export function FileStructure() {
       if (unlisten) {
         unlisten();
       }
-      invoke('stop_watch').catch(console.error);
+      invoke('stop_watch', { path: currentPath }).catch(console.error);
     };
   }, [currentPath]);
 
@@ -305,7 +303,7 @@ export function FileStructure() {
         setPathInput(activeTab.path);
       } catch (error) {
         console.error('Failed to load directory:', error);
-        toast.error(`Failed to load directory: ${error}`);
+        toast.error(`Failed to load directory: ${errorMessage(error)}`);
       } finally {
         setLoading(false);
       }
@@ -394,7 +392,7 @@ export function FileStructure() {
         });
         toast.success(result);
       } catch (error) {
-        toast.error(`Failed to open file: ${error}`);
+        toast.error(`Failed to open file: ${errorMessage(error)}`);
       }
     }
   };
@@ -432,7 +430,7 @@ export function FileStructure() {
       handleItemsDeleted();
       handleRefresh();
     } catch (error) {
-      toast.error(`Failed to delete: ${error}`);
+      toast.error(`Failed to delete: ${errorMessage(error)}`);
     } finally {
       setLoading(false);
     }
@@ -651,7 +649,7 @@ export function FileStructure() {
               );
               toast.success(result);
             } catch (error) {
-              toast.error(`Failed to open file: ${error}`);
+              toast.error(`Failed to open file: ${errorMessage(error)}`);
             }
           }
         };
@@ -668,7 +666,7 @@ export function FileStructure() {
             });
             handleRefresh();
           } catch (error) {
-            toast.error(`Failed to rename: ${error}`);
+            toast.error(`Failed to rename: ${errorMessage(error)}`);
           }
         };
 
@@ -682,7 +680,7 @@ export function FileStructure() {
             await invoke('delete_item', { path: fileItem.path });
             handleRefresh();
           } catch (error) {
-            toast.error(`Failed to delete: ${error}`);
+            toast.error(`Failed to delete: ${errorMessage(error)}`);
           }
         };
 
@@ -1006,7 +1004,7 @@ export function FileStructure() {
                             invoke('reveal_in_finder', { path: fileItem.path })
                               .then(() => toast.success('Revealed in Finder'))
                               .catch((error) =>
-                                toast.error(`Failed to reveal: ${error}`),
+                                toast.error(`Failed to reveal: ${errorMessage(error)}`),
                               )
                           }
                         >
@@ -1033,7 +1031,7 @@ export function FileStructure() {
                                 handleRefresh();
                               })
                               .catch((error) =>
-                                toast.error(`Failed to delete: ${error}`),
+                                toast.error(`Failed to delete: ${errorMessage(error)}`),
                               );
                           }}
                         >
@@ -4,6 +4,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { Button } from '@/components/ui/button';
 import { FileText, ExternalLink, AlertCircle } from 'lucide-react';
 import { toast } from 'sonner';
+import { errorMessage } from '@/lib/utils';
 
 interface PdfViewerProps {
   filePath: string;
@@ -32,7 +33,7 @@ export function PdfViewer({ filePath, fileName: _fileName }: PdfViewerProps) {
       });
       toast.success(result);
     } catch (error) {
-      toast.error(`Failed to open PDF: ${error}`);
+      toast.error(`Failed to open PDF: ${errorMessage(error)}`);
     }
   };
 